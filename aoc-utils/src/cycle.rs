@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The states of a simulation that eventually repeats itself: after `start`
+/// steps it loops round the same `length` states forever
+#[derive(Clone,Debug)]
+pub struct Cycle<T> {
+    /// Every state up to the end of the first loop, starting with the initial one
+    history: Vec<T>,
+    start: usize
+}
+
+impl<T> Cycle<T> {
+    /// Number of steps before the loop starts
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Number of steps round the loop
+    pub fn length(&self) -> usize {
+        self.history.len() - self.start
+    }
+
+    /// Every distinct state, in order, from the initial one to the end of the
+    /// first time round the loop
+    pub fn states(&self) -> &[T] {
+        &self.history
+    }
+
+    /// The state after `steps` steps, however large
+    pub fn get(&self, steps: usize) -> &T {
+        if steps < self.history.len() {
+            &self.history[steps]
+        }
+        else {
+            &self.history[self.start + (steps - self.start) % self.length()]
+        }
+    }
+}
+
+/// Runs `step` from `initial` until a state repeats. The states must
+/// eventually repeat, otherwise this never returns.
+pub fn find_cycle<T, F>(initial: T, step: F) -> Cycle<T> where T: Clone + Hash + Eq, F: FnMut(&T) -> T {
+    find_cycle_by_key(initial, step, T::clone)
+}
+
+/// Like [`find_cycle`], but compares states by a `key` (e.g. a cheap
+/// fingerprint) instead of the whole state. States with the same key are
+/// assumed to be the same.
+pub fn find_cycle_by_key<T, K, F, FK>(initial: T, mut step: F, mut key: FK) -> Cycle<T>
+    where K: Hash + Eq, F: FnMut(&T) -> T, FK: FnMut(&T) -> K
+{
+    let mut seen = HashMap::from([(key(&initial), 0)]);
+    let mut history = vec![initial];
+
+    loop {
+        let next = step(&history[history.len() - 1]);
+
+        if let Some(&start) = seen.get(&key(&next)) {
+            return Cycle { history, start };
+        }
+
+        seen.insert(key(&next), history.len());
+        history.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_cycle() {
+        // 0, 1, 2, 3, 4, 2, 3, 4, ...
+        let cycle = find_cycle(0, |n| if *n == 4 { 2 } else { n + 1 });
+        assert_eq!((cycle.start(), cycle.length()), (2, 3));
+        assert_eq!((0..11).map(|n| *cycle.get(n)).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 2, 3, 4, 2, 3, 4]);
+        assert_eq!(*cycle.get(1_000_000_000), 2 + (1_000_000_000 - 2) % 3);
+        assert_eq!(cycle.states(), &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let cycle = find_cycle(5, |n| n / 2);
+        assert_eq!((cycle.start(), cycle.length()), (3, 1));
+        assert_eq!(*cycle.get(100), 0);
+    }
+
+    #[test]
+    fn test_find_cycle_by_key() {
+        // the key only looks at the value modulo 3, so 0 and 3 look the same
+        let cycle = find_cycle_by_key(0, |n| n + 1, |n| n % 3);
+        assert_eq!((cycle.start(), cycle.length()), (0, 3));
+        assert_eq!(*cycle.get(7), 1);
+    }
+}
@@ -1,6 +1,7 @@
 use std::error::Error;
 use std::fmt;
 use std::num::{ParseIntError,TryFromIntError};
+use std::ops::Range;
 
 #[derive(Debug)]
 pub enum PuzzleErrorKind {
@@ -8,50 +9,326 @@ pub enum PuzzleErrorKind {
     InputError,
     ParseError,
     IntegerError,
+    InvalidArgument,
+    InputFileError(std::path::PathBuf, std::io::Error),
     IOError(std::io::Error)
 }
 
-#[derive(Debug)]
+impl fmt::Display for PuzzleErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleErrorKind::MissingInput => write!(f, "missing input"),
+            PuzzleErrorKind::InputError => write!(f, "invalid input"),
+            PuzzleErrorKind::ParseError => write!(f, "parse error"),
+            PuzzleErrorKind::IntegerError => write!(f, "integer error"),
+            PuzzleErrorKind::InvalidArgument => write!(f, "invalid argument"),
+            PuzzleErrorKind::InputFileError(path, _) => write!(f, "cannot read input {}", path.display()),
+            PuzzleErrorKind::IOError(_) => write!(f, "I/O error")
+        }
+    }
+}
+
+/// Where in the puzzle input an error was found
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct Location {
+    /// Line number, starting from 1
+    pub line: usize,
+    /// Byte range of the offending text within the line
+    pub span: Range<usize>,
+    /// The whole line of input
+    pub snippet: String
+}
+
+impl Location {
+    /// Location of the whole of `line`
+    pub fn line(line_number: usize, line: &str) -> Self {
+        Self::span(line_number, line, 0..line.len())
+    }
+
+    /// Location of `span` (a byte range) within `line`. The span is widened
+    /// to whole characters if it starts or ends part way through one.
+    pub fn span(line_number: usize, line: &str, span: Range<usize>) -> Self {
+        Self {
+            line: line_number,
+            span: clamp_to_chars(line, span),
+            snippet: line.to_owned()
+        }
+    }
+
+    /// Location of `token` within `line`. The token must be a slice of the
+    /// line (e.g. from `split` or `trim`), otherwise the whole line is used.
+    pub fn token(line_number: usize, line: &str, token: &str) -> Self {
+        let line_start = line.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+
+        if token_start >= line_start && token_start + token.len() <= line_start + line.len() {
+            let offset = token_start - line_start;
+            Self::span(line_number, line, offset..offset + token.len())
+        }
+        else {
+            Self::line(line_number, line)
+        }
+    }
+
+    /// Column of the start of the span, counting characters from 1
+    pub fn column(&self) -> usize {
+        let span = clamp_to_chars(&self.snippet, self.span.clone());
+        self.snippet[..span.start].chars().count() + 1
+    }
+}
+
+/// Shrinks `span` to fit in `line` and widens it to whole characters, so it
+/// can always be sliced
+fn clamp_to_chars(line: &str, span: Range<usize>) -> Range<usize> {
+    let mut end = span.end.min(line.len());
+    while !line.is_char_boundary(end) {
+        end += 1;
+    }
+
+    let mut start = span.start.min(end);
+    while !line.is_char_boundary(start) {
+        start -= 1;
+    }
+
+    start..end
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = self.line.to_string().len();
+        let underline = self.snippet[clamp_to_chars(&self.snippet, self.span.clone())].chars().count().max(1);
+
+        writeln!(f, "{:gutter$}--> line {}, column {}", "", self.line, self.column())?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{:gutter$} | {:indent$}{}", "", "", "^".repeat(underline), indent = self.column() - 1)
+    }
+}
+
 pub struct PuzzleError {
-    kind: PuzzleErrorKind
+    kind: PuzzleErrorKind,
+    message: Option<String>,
+    location: Option<Location>,
+    source: Option<Box<dyn Error + Send + Sync>>
 }
 
 impl PuzzleError {
+    pub fn new<S: Into<String>>(kind: PuzzleErrorKind, message: S) -> Self {
+        Self::from(kind).with_message(message)
+    }
+
+    /// Shorthand for a [`PuzzleErrorKind::ParseError`] with a message
+    pub fn parse<S: Into<String>>(message: S) -> Self {
+        Self::new(PuzzleErrorKind::ParseError, message)
+    }
+
     pub fn kind(&self) -> &PuzzleErrorKind {
         &self.kind
     }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// The kind and message on one line, without the location or causes
+    pub fn summary(&self) -> String {
+        match &self.message {
+            Some(message) => format!("{}: {}", self.kind, message),
+            None => self.kind.to_string()
+        }
+    }
+
+    pub fn with_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Records the lower level error that caused this one
+    pub fn with_source<E>(mut self, source: E) -> Self where E: Error + Send + Sync + 'static {
+        self.source = Some(Box::new(source));
+        self
+    }
+}
+
+/// Attaches messages and locations to errors on their way out of a parser,
+/// e.g. `value.parse::<u32>().context("bad count").at_token(n, line, value)`
+pub trait ErrorContext<T> {
+    fn context<S: Into<String>>(self, message: S) -> Result<T, PuzzleError>;
+    fn at(self, location: Location) -> Result<T, PuzzleError>;
+
+    fn at_line(self, line_number: usize, line: &str) -> Result<T, PuzzleError> where Self: Sized {
+        self.at(Location::line(line_number, line))
+    }
+
+    fn at_token(self, line_number: usize, line: &str, token: &str) -> Result<T, PuzzleError> where Self: Sized {
+        self.at(Location::token(line_number, line, token))
+    }
+}
+
+impl<T, E> ErrorContext<T> for Result<T, E> where E: Into<PuzzleError> {
+    fn context<S: Into<String>>(self, message: S) -> Result<T, PuzzleError> {
+        self.map_err(|e| e.into().with_message(message))
+    }
+
+    /// Sets the location, unless a more specific one was already attached
+    fn at(self, location: Location) -> Result<T, PuzzleError> {
+        self.map_err(|e| {
+            let e: PuzzleError = e.into();
+            if e.location.is_some() {
+                e
+            }
+            else {
+                e.with_location(location)
+            }
+        })
+    }
 }
 
 impl From<PuzzleErrorKind> for PuzzleError {
     fn from(kind: PuzzleErrorKind) -> Self {
-        Self { kind }
+        Self { kind, message: None, location: None, source: None }
     }
 }
 
 impl From<ParseIntError> for PuzzleError {
-    fn from(_: ParseIntError) -> Self {
-        Self { kind: PuzzleErrorKind::ParseError }
+    fn from(e: ParseIntError) -> Self {
+        Self::from(PuzzleErrorKind::ParseError).with_source(e)
     }
 }
 
 impl From<TryFromIntError> for PuzzleError {
-    fn from(_: TryFromIntError) -> Self {
-        Self { kind: PuzzleErrorKind::IntegerError }
+    fn from(e: TryFromIntError) -> Self {
+        Self::from(PuzzleErrorKind::IntegerError).with_source(e)
     }
 }
 
 impl From<std::io::Error> for PuzzleError {
     fn from(io_error: std::io::Error) -> Self {
-        Self {
-            kind: PuzzleErrorKind::IOError(io_error)
-        }
+        Self::from(PuzzleErrorKind::IOError(io_error))
     }
 }
 
 impl fmt::Display for PuzzleError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "PuzzleError: {:?}", self.kind)
+        write!(f, "{}", self.summary())?;
+
+        if let Some(location) = &self.location {
+            write!(f, "\n{}", location)?;
+        }
+
+        Ok(())
+    }
+}
+
+// `main` prints errors with `Debug`, so make that a readable report of the
+// whole chain of causes
+impl fmt::Debug for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)?;
+
+        let mut source = self.source();
+        while let Some(e) = source {
+            write!(f, "\ncaused by: {}", e)?;
+            source = e.source();
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            PuzzleErrorKind::InputFileError(_, e) | PuzzleErrorKind::IOError(e) => Some(e),
+            _ => self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+        }
     }
 }
 
-impl Error for PuzzleError {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_without_context() {
+        let e = PuzzleError::from(PuzzleErrorKind::ParseError);
+        assert_eq!(e.to_string(), "parse error");
+    }
+
+    #[test]
+    fn test_display_with_location() {
+        let line = "50 98 x2";
+        let e: PuzzleError = "x2".parse::<i64>()
+            .context("mapping values must be numbers")
+            .at_token(4, line, &line[6..])
+            .unwrap_err();
+
+        assert_eq!(e.location().map(Location::column), Some(7));
+        assert_eq!(e.to_string(), [
+            "parse error: mapping values must be numbers",
+            " --> line 4, column 7",
+            "  |",
+            "4 | 50 98 x2",
+            "  |       ^^",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_summary() {
+        let e = PuzzleError::parse("bad count").with_location(Location::line(3, "abc"));
+        assert_eq!(e.summary(), "parse error: bad count");
+        assert_eq!(PuzzleError::from(PuzzleErrorKind::ParseError).summary(), "parse error");
+    }
+
+    #[test]
+    fn test_first_location_wins() {
+        let e: PuzzleError = Err::<(), _>(PuzzleErrorKind::ParseError)
+            .at(Location::span(12, "abc def", 4..7))
+            .at_line(12, "abc def")
+            .unwrap_err();
+
+        assert_eq!(e.location().map(|l| l.span.clone()), Some(4..7));
+    }
+
+    #[test]
+    fn test_source_chain() {
+        let inner: PuzzleError = "abc".parse::<u32>().unwrap_err().into();
+        let outer = PuzzleError::parse("bad workflow").with_source(inner);
+
+        let source = outer.source().expect("Should have a source");
+        assert_eq!(source.to_string(), "parse error");
+        assert!(source.source().is_some_and(|e| e.is::<ParseIntError>()));
+        assert_eq!(format!("{:?}", outer), [
+            "parse error: bad workflow",
+            "caused by: parse error",
+            "caused by: invalid digit found in string",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_token_outside_line() {
+        let location = Location::token(1, "abc", "xyz");
+        assert_eq!(location.span, 0..3);
+    }
+
+    #[test]
+    fn test_span_inside_multibyte_char() {
+        // `é` is bytes 1..3
+        let location = Location::span(1, "zéro", 2..3);
+        assert_eq!(location.span, 1..3);
+        assert_eq!(location.column(), 2);
+
+        let location = Location { line: 1, span: 2..2, snippet: String::from("zéro") };
+        assert_eq!(location.column(), 2);
+        assert!(location.to_string().ends_with("|  ^"));
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt;
+use std::ops::{Add,AddAssign,Mul,Neg,Sub,SubAssign};
+
+/// A point (or a vector between points). `y` grows downwards, as it does when
+/// reading a grid from the puzzle input.
+#[derive(Clone,Copy,Debug,Default,Eq,Hash,Ord,PartialEq,PartialOrd)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Sum of the distances along each axis
+    pub fn manhattan_distance(&self, other: &Self) -> T where T: Copy + Ord + Add<Output = T> + Sub<Output = T> {
+        let dx = self.x.max(other.x) - self.x.min(other.x);
+        let dy = self.y.max(other.y) - self.y.min(other.y);
+        dx + dy
+    }
+}
+
+impl Point<usize> {
+    /// Moves by `(dx, dy)`, or `None` if that would go below zero
+    pub fn checked_offset(&self, dx: isize, dy: isize) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?
+        })
+    }
+
+    /// Moves one step in `direction`, or `None` if that would go below zero
+    pub fn checked_step(&self, direction: Direction) -> Option<Self> {
+        let delta = direction.delta();
+        self.checked_offset(delta.x, delta.y)
+    }
+
+    /// Moves by `(dx, dy)`, or `None` if that leaves a `width` x `height` area
+    /// with its corner at the origin
+    pub fn offset_within(&self, dx: isize, dy: isize, width: usize, height: usize) -> Option<Self> {
+        self.checked_offset(dx, dy).filter(|p| p.x < width && p.y < height)
+    }
+
+    /// Moves one step in `direction`, or `None` if that leaves a `width` x
+    /// `height` area with its corner at the origin
+    pub fn step_within(&self, direction: Direction, width: usize, height: usize) -> Option<Self> {
+        let delta = direction.delta();
+        self.offset_within(delta.x, delta.y, width, height)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> Add for Point<T> where T: Add<Output = T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T> Sub for Point<T> where T: Sub<Output = T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> AddAssign for Point<T> where T: AddAssign {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T> SubAssign for Point<T> where T: SubAssign {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T> Mul<T> for Point<T> where T: Mul<Output = T> + Copy {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T> Neg for Point<T> where T: Neg<Output = T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T> fmt::Display for Point<T> where T: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A compass direction, with north towards the top of the puzzle input
+#[derive(Clone,Copy,Debug,Eq,Hash,Ord,PartialEq,PartialOrd)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West
+}
+
+impl Direction {
+    /// Every direction, clockwise from north
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// The direction after a quarter turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South
+        }
+    }
+
+    /// The direction after a quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::North | Direction::South)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+
+    /// One step in this direction
+    pub fn delta(self) -> Point<isize> {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0)
+        }
+    }
+}
+
+impl From<Direction> for Point<isize> {
+    fn from(direction: Direction) -> Self {
+        direction.delta()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(1, 5);
+        assert_eq!(a + b, Point::new(4, 3));
+        assert_eq!(a - b, Point::new(2, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+
+        let mut c = a;
+        c += b;
+        c -= Point::new(4, 0);
+        assert_eq!(c, Point::new(0, 3));
+    }
+
+    #[test]
+    fn test_manhattan_distance() {
+        assert_eq!(Point::new(1usize, 6).manhattan_distance(&Point::new(5, 2)), 8);
+        assert_eq!(Point::new(-1isize, 2).manhattan_distance(&Point::new(3, -2)), 8);
+    }
+
+    #[test]
+    fn test_checked_offset() {
+        let p = Point::new(0usize, 2);
+        assert_eq!(p.checked_offset(-1, 0), None);
+        assert_eq!(p.checked_step(Direction::South), Some(Point::new(0, 3)));
+        assert_eq!(p.step_within(Direction::South, 3, 3), None);
+        assert_eq!(p.offset_within(2, -2, 3, 3), Some(Point::new(2, 0)));
+    }
+
+    #[test]
+    fn test_turns() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.reverse());
+            assert_eq!(d.delta() + d.reverse().delta(), Point::default());
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+    }
+}
@@ -0,0 +1,328 @@
+use std::fmt;
+use std::ops::{Index,IndexMut};
+use std::str::FromStr;
+use crate::error::{ErrorContext,PuzzleError,PuzzleErrorKind};
+use crate::geom::Point;
+use crate::result::PuzzleResult;
+
+const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const NEIGHBOURS_8: [(isize, isize); 8] = [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+
+/// A rectangular grid of cells, stored row by row and indexed by `(x, y)`
+/// with `(0, 0)` in the top left corner
+#[derive(Clone,Debug,Eq,Hash,PartialEq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> PuzzleResult<Self> {
+        if width.checked_mul(height) != Some(cells.len()) {
+            return Err(PuzzleError::new(PuzzleErrorKind::InvalidArgument,
+                format!("{} cells do not fill a {}x{} grid", cells.len(), width, height)));
+        }
+
+        Ok(Self { cells, width, height })
+    }
+
+    /// Creates a grid with every cell set to `value`
+    pub fn filled(width: usize, height: usize, value: T) -> Self where T: Clone {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height
+        }
+    }
+
+    /// Parses one row per line, converting each character with `f`. Trailing
+    /// blank lines and CRLF line endings are ignored.
+    pub fn parse_with<F>(input: &str, mut f: F) -> PuzzleResult<Self> where F: FnMut(char) -> PuzzleResult<T> {
+        let lines: Vec<&str> = input.trim_end_matches(['\r', '\n']).lines().collect();
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (index, line) in lines.iter().enumerate() {
+            let before = cells.len();
+
+            for (offset, c) in line.char_indices() {
+                cells.push(f(c).at_token(index + 1, line, &line[offset..offset + c.len_utf8()])?);
+            }
+
+            if cells.len() - before != width {
+                return Err(PuzzleError::parse(format!("expected a row of width {}, found {}", width, cells.len() - before)))
+                    .at_line(index + 1, line);
+            }
+        }
+
+        Self::new(width, lines.len(), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            self.cells.get(y * self.width + x)
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            self.cells.get_mut(y * self.width + x)
+        }
+        else {
+            None
+        }
+    }
+
+    /// Sets a cell, returning its previous value, or `None` (leaving the grid
+    /// unchanged) if `(x, y)` is outside the grid
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> Option<T> {
+        self.get_mut(x, y).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Moves from `(x, y)` by `(dx, dy)`, if that stays inside the grid
+    pub fn offset(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        self.contains(x, y).then_some((x, y))
+    }
+
+    /// The orthogonal neighbours of `(x, y)` that are inside the grid, clockwise
+    /// from north
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS_4.iter().filter_map(move |d| self.offset((x, y), *d))
+    }
+
+    /// The orthogonal and diagonal neighbours of `(x, y)` that are inside the
+    /// grid, clockwise from north
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS_8.iter().filter_map(move |d| self.offset((x, y), *d))
+    }
+
+    /// Every `(x, y)` in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell along with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell (row by row) that matches `predicate`
+    pub fn position<P>(&self, mut predicate: P) -> Option<(usize, usize)> where P: FnMut(&T) -> bool {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(p, _)| p)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, so give it an empty slice instead
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width).take(self.height))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).filter_map(|x| self.column(x))
+    }
+
+    /// Converts every cell with `f`
+    pub fn map<U, F>(&self, f: F) -> Grid<U> where F: FnMut(&T) -> U {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height
+        }
+    }
+
+    /// Builds a grid of the given size by looking up each new cell's source
+    /// position in this grid
+    fn remap<F>(&self, width: usize, height: usize, source: F) -> Self where T: Clone, F: Fn(usize, usize) -> (usize, usize) {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (sx, sy) = source(x, y);
+                self[(sx, sy)].clone()
+            })
+            .collect();
+
+        Self { cells, width, height }
+    }
+
+    /// Swaps rows and columns
+    pub fn transpose(&self) -> Self where T: Clone {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self where T: Clone {
+        let height = self.height;
+        self.remap(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    pub fn rotate_anticlockwise(&self) -> Self where T: Clone {
+        let width = self.width;
+        self.remap(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirrors the grid left to right
+    pub fn flip_horizontal(&self) -> Self where T: Clone {
+        let width = self.width;
+        self.remap(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirrors the grid top to bottom
+    pub fn flip_vertical(&self) -> Self where T: Clone {
+        let height = self.height;
+        self.remap(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the {}x{} grid", x, y, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the {}x{} grid", x, y, width, height))
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point<usize>) -> &Self::Output {
+        &self[(p.x, p.y)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, p: Point<usize>) -> &mut Self::Output {
+        &mut self[(p.x, p.y)]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Ok)
+    }
+}
+
+impl<T> fmt::Display for Grid<T> where T: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        "abc\r\ndef\r\n\r\n".parse().expect("Should parse")
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        let e = "abc\nde\n".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(e.location().map(|l| l.line), Some(2));
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let e = Grid::parse_with("12\n3x\n", |c| c.to_digit(10).ok_or(PuzzleError::parse("not a digit")))
+            .unwrap_err();
+        assert_eq!(e.location().map(|l| (l.line, l.column())), Some((2, 2)));
+    }
+
+    #[test]
+    fn test_set() {
+        let mut grid = sample();
+        assert_eq!(grid.set(0, 1, 'x'), Some('d'));
+        assert_eq!(grid.set(0, 2, 'x'), None);
+        assert_eq!(grid[(0, 1)], 'x');
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::filled(3, 3, 0);
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours4(1, 1).count(), 4);
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+        assert_eq!(grid.neighbours8(2, 2).collect::<Vec<_>>(), vec![(2, 1), (1, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = sample();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&['a', 'b', 'c'][..], &['d', 'e', 'f'][..]]);
+        assert_eq!(grid.column(1).map(|c| c.collect::<String>()), Some(String::from("be")));
+        assert_eq!(grid.columns().map(|c| c.collect::<String>()).collect::<Vec<_>>(), vec!["ad", "be", "cf"]);
+        assert!(grid.row(2).is_none());
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid = sample();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid: Grid<char> = "".parse().expect("Should parse");
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+}
@@ -1,16 +1,307 @@
 use std::env;
+use std::fmt;
 use std::fs::File;
-use std::io::BufReader;
-use crate::error::PuzzleErrorKind;
+use std::io::{self,BufRead,BufReader};
+use std::path::{Path,PathBuf};
+use std::str::FromStr;
+use crate::error::{PuzzleError,PuzzleErrorKind};
 use crate::result::PuzzleResult;
 
-pub fn get_puzzle_input() -> PuzzleResult<BufReader<File>> {
-    let filename = env::args()
-        .nth(1)
-        .ok_or_else(|| PuzzleErrorKind::MissingInput)?;
+/// Which part(s) of a puzzle should be solved
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum PuzzlePart {
+    One,
+    Two,
+    Both
+}
+
+impl PuzzlePart {
+    /// The part to solve when none is given on the command line, which is
+    /// whatever the crate's `part2` feature asks for.
+    pub fn from_feature(part2: bool) -> Self {
+        if part2 {
+            PuzzlePart::Two
+        }
+        else {
+            PuzzlePart::One
+        }
+    }
+
+    pub fn includes_part1(&self) -> bool {
+        matches!(self, PuzzlePart::One | PuzzlePart::Both)
+    }
+
+    pub fn includes_part2(&self) -> bool {
+        matches!(self, PuzzlePart::Two | PuzzlePart::Both)
+    }
+}
+
+impl FromStr for PuzzlePart {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(PuzzlePart::One),
+            "2" => Ok(PuzzlePart::Two),
+            "both" => Ok(PuzzlePart::Both),
+            _ => Err(PuzzleErrorKind::InvalidArgument.into())
+        }
+    }
+}
+
+/// Directory that puzzle inputs are looked for in when none is given
+pub const INPUTS_DIR: &str = "inputs";
+
+/// Where to read a puzzle's input from
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub enum InputSource {
+    Stdin,
+    Path(PathBuf)
+}
+
+impl InputSource {
+    /// Works out the input source from the command line argument (if any),
+    /// where `-` means stdin and no argument at all means the conventional
+    /// location for `crate_name` (see [`conventional_input_path`]).
+    pub fn from_arg(arg: Option<&str>, crate_name: &str) -> PuzzleResult<Self> {
+        match arg {
+            Some("-") => Ok(InputSource::Stdin),
+            Some(path) => Ok(InputSource::Path(path.into())),
+            None => Ok(InputSource::Path(conventional_input_path(INPUTS_DIR, crate_name)?))
+        }
+    }
+
+    pub fn open(&self) -> PuzzleResult<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(BufReader::new(io::stdin()))),
+            InputSource::Path(path) => {
+                let input = File::open(path)
+                    .map_err(|e| PuzzleErrorKind::InputFileError(path.clone(), e))?;
+
+                Ok(Box::new(BufReader::new(input)))
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Path(path) => write!(f, "{}", path.display())
+        }
+    }
+}
+
+/// The conventional location of a puzzle's input, which is named after the
+/// day number at the end of the crate name, e.g. `inputs/day07.txt` for
+/// `day7`.
+pub fn conventional_input_path<P: AsRef<Path>>(inputs_dir: P, crate_name: &str) -> PuzzleResult<PathBuf> {
+    let digits = crate_name
+        .trim_start_matches(|c: char| !c.is_ascii_digit());
+
+    let day: u32 = digits
+        .parse()
+        .map_err(|_| PuzzleErrorKind::MissingInput)?;
+
+    Ok(inputs_dir.as_ref().join(format!("day{:02}.txt", day)))
+}
+
+/// An option that a particular puzzle understands on top of the input file
+/// and `--part`
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum PuzzleOption {
+    /// `--name` on its own
+    Flag(&'static str),
+    /// `--name VALUE` or `--name=VALUE`
+    Value(&'static str)
+}
+
+impl PuzzleOption {
+    fn name(&self) -> &'static str {
+        match self {
+            PuzzleOption::Flag(name) | PuzzleOption::Value(name) => name
+        }
+    }
+}
+
+/// Command line arguments understood by every puzzle, i.e.
+/// `[<input file>|-] [--part 1|2|both]`, plus any options of its own
+#[derive(Debug,Default,Eq,PartialEq)]
+pub struct PuzzleArgs {
+    filename: Option<String>,
+    part: Option<PuzzlePart>,
+    /// The puzzle's own options that were given, with their values
+    options: Vec<(&'static str, Option<String>)>
+}
+
+impl PuzzleArgs {
+    /// Parses `args`, accepting `options` as well as the usual arguments
+    pub fn parse<I>(args: I, options: &[PuzzleOption]) -> PuzzleResult<Self> where I: IntoIterator<Item = String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.strip_prefix("--") {
+                Some(option) => match option.split_once('=') {
+                    Some((name, value)) => (Some(name), Some(value.to_owned())),
+                    None => (Some(option), None)
+                },
+                None => (None, None)
+            };
+
+            match name {
+                Some("part") => {
+                    let value = inline_value
+                        .or_else(|| args.next())
+                        .ok_or(PuzzleError::new(PuzzleErrorKind::InvalidArgument, "`--part` needs 1, 2 or both"))?;
+                    parsed.part = Some(value.parse()?);
+                },
+                Some(name) => {
+                    let option = options
+                        .iter()
+                        .find(|o| o.name() == name)
+                        .ok_or(PuzzleError::new(PuzzleErrorKind::InvalidArgument, format!("unknown option `--{}`", name)))?;
+
+                    let value = match option {
+                        PuzzleOption::Flag(_) if inline_value.is_some() =>
+                            return Err(PuzzleError::new(PuzzleErrorKind::InvalidArgument, format!("`--{}` doesn't take a value", name))),
+                        PuzzleOption::Flag(_) => None,
+                        PuzzleOption::Value(_) => Some(inline_value
+                            .or_else(|| args.next())
+                            .ok_or(PuzzleError::new(PuzzleErrorKind::InvalidArgument, format!("`--{}` needs a value", name)))?)
+                    };
+
+                    parsed.options.retain(|(n, _)| *n != option.name());
+                    parsed.options.push((option.name(), value));
+                },
+                None if (arg.starts_with('-') && arg != "-") || parsed.filename.is_some() => {
+                    return Err(PuzzleErrorKind::InvalidArgument.into());
+                },
+                None => parsed.filename = Some(arg)
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Parses the program's own command line, accepting `options` as well as
+    /// the usual arguments
+    pub fn from_env(options: &[PuzzleOption]) -> PuzzleResult<Self> {
+        Self::parse(env::args().skip(1), options)
+    }
+
+    /// Where to read the input from, falling back to the conventional input
+    /// file for `crate_name`
+    pub fn input_source(&self, crate_name: &str) -> PuzzleResult<InputSource> {
+        InputSource::from_arg(self.filename.as_deref(), crate_name)
+    }
+
+    /// The part(s) selected with `--part`, or `default` if there wasn't one
+    pub fn part(&self, default: PuzzlePart) -> PuzzlePart {
+        self.part.unwrap_or(default)
+    }
+
+    /// Whether the flag `--name` was given
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(n, _)| *n == name)
+    }
+
+    /// The value given for `--name`, if it was
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(n, _)| *n == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> PuzzleResult<PuzzleArgs> {
+        PuzzleArgs::parse(args.iter().map(|s| s.to_string()), &[])
+    }
+
+    #[test]
+    fn test_filename_only() {
+        let args = parse(&["input.txt"]).expect("Should parse");
+        assert_eq!(args.filename.as_deref(), Some("input.txt"));
+        assert_eq!(args.part, None);
+    }
+
+    #[test]
+    fn test_part_before_and_after_filename() {
+        let args = parse(&["--part", "both", "input.txt"]).expect("Should parse");
+        assert_eq!(args.filename.as_deref(), Some("input.txt"));
+        assert_eq!(args.part, Some(PuzzlePart::Both));
+
+        let args = parse(&["input.txt", "--part=2"]).expect("Should parse");
+        assert_eq!(args.filename.as_deref(), Some("input.txt"));
+        assert_eq!(args.part, Some(PuzzlePart::Two));
+    }
+
+    #[test]
+    fn test_stdin_argument() {
+        let args = parse(&["-", "--part", "1"]).expect("Should parse");
+        assert_eq!(args.filename.as_deref(), Some("-"));
+        assert_eq!(InputSource::from_arg(args.filename.as_deref(), "day1").expect("Should resolve"), InputSource::Stdin);
+    }
+
+    #[test]
+    fn test_input_source() {
+        assert_eq!(
+            InputSource::from_arg(Some("other.txt"), "day7").expect("Should resolve"),
+            InputSource::Path(PathBuf::from("other.txt"))
+        );
+        assert_eq!(
+            InputSource::from_arg(None, "day7").expect("Should resolve"),
+            InputSource::Path(PathBuf::from("inputs/day07.txt"))
+        );
+    }
+
+    #[test]
+    fn test_conventional_input_path() {
+        assert_eq!(conventional_input_path("inputs", "day19").expect("Should resolve"), PathBuf::from("inputs/day19.txt"));
+        assert_eq!(conventional_input_path("x", "puzzle3").expect("Should resolve"), PathBuf::from("x/day03.txt"));
+        assert!(conventional_input_path("inputs", "aoc-utils").is_err());
+    }
+
+    #[test]
+    fn test_open_reports_path() {
+        let source = InputSource::Path(PathBuf::from("does/not/exist.txt"));
+        let error = source.open().err().expect("Should not open");
+        assert!(error.to_string().contains("does/not/exist.txt"));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(parse(&["input.txt", "--part"]).is_err());
+        assert!(parse(&["input.txt", "--part", "3"]).is_err());
+        assert!(parse(&["input.txt", "--verbose"]).is_err());
+        assert!(parse(&["-v"]).is_err());
+        assert!(parse(&["input.txt", "other.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_puzzle_options() {
+        let options = [PuzzleOption::Flag("lenient"), PuzzleOption::Value("limits")];
+        let parse = |args: &[&str]| PuzzleArgs::parse(args.iter().map(|s| s.to_string()), &options);
+
+        let args = parse(&["input.txt", "--lenient", "--limits", "1 red", "--part=2"]).expect("Should parse");
+        assert_eq!(args.filename.as_deref(), Some("input.txt"));
+        assert_eq!(args.part(PuzzlePart::One), PuzzlePart::Two);
+        assert!(args.flag("lenient"));
+        assert_eq!(args.value("limits"), Some("1 red"));
 
-    let input = File::open(filename)
-        .map_err(|_| PuzzleErrorKind::InputError)?;
+        let args = parse(&["--limits=2 blue", "--limits=3 green"]).expect("Should parse");
+        assert!(!args.flag("lenient"));
+        assert_eq!(args.value("limits"), Some("3 green"));
+        assert_eq!(args.part(PuzzlePart::One), PuzzlePart::One);
 
-    Ok(BufReader::new(input))
+        assert!(parse(&["--limits"]).is_err());
+        assert!(parse(&["--lenient=yes"]).is_err());
+        assert!(parse(&["--strict"]).is_err());
+    }
 }
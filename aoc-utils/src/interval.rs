@@ -0,0 +1,450 @@
+use std::ops::Range;
+use crate::math::Integer;
+
+/// The part of `range` that is also in `bounds`, if any
+fn clip<T: Ord + Copy>(range: &Range<T>, bounds: &Range<T>) -> Option<Range<T>> {
+    let clipped = range.start.max(bounds.start)..range.end.min(bounds.end);
+    (!clipped.is_empty()).then_some(clipped)
+}
+
+/// A set of values, stored as sorted ranges that don't overlap. Ranges that
+/// touch are merged, so equal sets always hold the same ranges.
+#[derive(Clone,Debug,Eq,Hash,PartialEq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T> IntervalSet<T> where T: Ord + Copy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The ranges in the set, in order
+    pub fn iter(&self) -> std::slice::Iter<'_, Range<T>> {
+        self.ranges.iter()
+    }
+
+    /// The smallest value in the set
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= *value);
+        self.ranges.get(index).is_some_and(|r| r.start <= *value)
+    }
+
+    /// Adds every value in `range`, merging it with any ranges it overlaps or
+    /// touches
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if first < last {
+            range.start.min(self.ranges[first].start)..range.end.max(self.ranges[last - 1].end)
+        }
+        else {
+            range
+        };
+
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Takes every value in `range` out of the set
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first == last {
+            return;
+        }
+
+        // keep whatever sticks out either side of the removed range
+        let left = self.ranges[first].start..range.start;
+        let right = range.end..self.ranges[last - 1].end;
+
+        self.ranges.splice(first..last, [left, right].into_iter().filter(|r| !r.is_empty()));
+    }
+
+    /// Values in either set
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter().cloned());
+        union
+    }
+
+    /// Values in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut left, mut right) = (self.ranges.iter().peekable(), other.ranges.iter().peekable());
+
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            ranges.extend(clip(l, r));
+
+            // move past whichever range finishes first, as it can't overlap
+            // anything else in the other set
+            if l.end < r.end {
+                left.next();
+            }
+            else {
+                right.next();
+            }
+        }
+
+        // the pieces are already sorted, and can't touch as that would have
+        // meant two touching ranges in one of the sets
+        Self { ranges }
+    }
+
+    /// Values in this set but not in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other {
+            difference.remove(range.clone());
+        }
+        difference
+    }
+
+    /// Splits the set into the values below `at` and the values from `at`
+    /// upwards
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = self.clone();
+        let mut above = self.clone();
+
+        if let Some(start) = self.min() {
+            below.remove(at.max(start)..self.ranges[self.ranges.len() - 1].end);
+            above.remove(start..at);
+        }
+
+        (below, above)
+    }
+}
+
+impl<T> IntervalSet<T> where T: Integer {
+    /// How many values are in the set
+    pub fn total_length(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |total, r| total + (r.end - r.start))
+    }
+}
+
+impl<T> From<Range<T>> for IntervalSet<T> where T: Ord + Copy {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T> Extend<Range<T>> for IntervalSet<T> where T: Ord + Copy {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T> FromIterator<Range<T>> for IntervalSet<T> where T: Ord + Copy {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+/// Ranges of keys mapped to values, stored as sorted ranges that don't
+/// overlap. Ranges that touch and have the same value are merged.
+#[derive(Clone,Debug,Eq,Hash,PartialEq)]
+pub struct IntervalMap<T, V> {
+    entries: Vec<(Range<T>, V)>
+}
+
+impl<T, V> Default for IntervalMap<T, V> {
+    fn default() -> Self {
+        Self { entries: Vec::new() }
+    }
+}
+
+impl<T, V> IntervalMap<T, V> where T: Ord + Copy, V: Clone + Eq {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The ranges in the map and their values, in order
+    pub fn iter(&self) -> impl Iterator<Item = (&Range<T>, &V)> {
+        self.entries.iter().map(|(r, v)| (r, v))
+    }
+
+    pub fn get(&self, key: &T) -> Option<&V> {
+        let index = self.entries.partition_point(|(r, _)| r.end <= *key);
+        self.entries
+            .get(index)
+            .filter(|(r, _)| r.start <= *key)
+            .map(|(_, v)| v)
+    }
+
+    /// Maps every key in `range` to `value`, replacing anything already there
+    pub fn insert(&mut self, range: Range<T>, value: V) {
+        if range.is_empty() {
+            return;
+        }
+
+        self.remove(range.clone());
+
+        let mut index = self.entries.partition_point(|(r, _)| r.end <= range.start);
+        self.entries.insert(index, (range, value));
+
+        // merge with the neighbours if they carry on with the same value
+        if index + 1 < self.entries.len() && self.touches_next(index) {
+            let (next, _) = self.entries.remove(index + 1);
+            self.entries[index].0.end = next.end;
+        }
+
+        if index > 0 && self.touches_next(index - 1) {
+            let (this, _) = self.entries.remove(index);
+            index -= 1;
+            self.entries[index].0.end = this.end;
+        }
+    }
+
+    fn touches_next(&self, index: usize) -> bool {
+        let (this, this_value) = &self.entries[index];
+        let (next, next_value) = &self.entries[index + 1];
+        this.end == next.start && this_value == next_value
+    }
+
+    /// Unmaps every key in `range`
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.entries.partition_point(|(r, _)| r.end <= range.start);
+        let last = self.entries.partition_point(|(r, _)| r.start < range.end);
+        if first == last {
+            return;
+        }
+
+        // keep whatever sticks out either side of the removed range
+        let (first_range, first_value) = &self.entries[first];
+        let (last_range, last_value) = &self.entries[last - 1];
+        let left = (first_range.start..range.start, first_value.clone());
+        let right = (range.end..last_range.end, last_value.clone());
+
+        self.entries.splice(first..last, [left, right].into_iter().filter(|(r, _)| !r.is_empty()));
+    }
+
+    /// The parts of `range` that are mapped, and what they are mapped to
+    pub fn overlapping(&self, range: Range<T>) -> impl Iterator<Item = (Range<T>, &V)> {
+        let first = self.entries.partition_point(|(r, _)| r.end <= range.start);
+        self.entries[first..]
+            .iter()
+            .take_while(move |(r, _)| r.start < range.end)
+            .filter_map(move |(r, v)| clip(r, &range).map(|r| (r, v)))
+    }
+
+    /// Every key that is mapped to something
+    pub fn domain(&self) -> IntervalSet<T> {
+        self.entries
+            .iter()
+            .map(|(r, _)| r.clone())
+            .collect()
+    }
+
+    /// The parts of `range` that aren't mapped to anything
+    pub fn gaps(&self, range: Range<T>) -> IntervalSet<T> {
+        IntervalSet::from(range).difference(&self.domain())
+    }
+
+    /// Splits the map into the keys below `at` and the keys from `at` upwards
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = self.clone();
+        let mut above = self.clone();
+
+        if let (Some((first, _)), Some((last, _))) = (self.entries.first(), self.entries.last()) {
+            below.remove(at.max(first.start)..last.end);
+            above.remove(first.start..at);
+        }
+
+        (below, above)
+    }
+}
+
+impl<T, V> FromIterator<(Range<T>, V)> for IntervalMap<T, V> where T: Ord + Copy, V: Clone + Eq {
+    fn from_iter<I: IntoIterator<Item = (Range<T>, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (range, value) in iter {
+            map.insert(range, value);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn set(ranges: &[Range<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert_coalesces() {
+        let mut s = set(&[0..5, 10..15]);
+        s.insert(5..10);
+        assert_eq!(s.iter().cloned().collect::<Vec<_>>(), vec![0..15]);
+
+        // equal and contained ranges change nothing
+        s.insert(0..15);
+        s.insert(3..4);
+        assert_eq!(s, set(&[0..15]));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut s = set(&[0..10, 20..30]);
+        s.remove(5..25);
+        assert_eq!(s, set(&[0..5, 25..30]));
+        s.remove(0..5);
+        assert_eq!(s, set(&[25..30]));
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(set(&[0..15]).intersection(&set(&[11..45])), set(&[11..15]));
+        assert_eq!(set(&[11..45]).intersection(&set(&[0..15])), set(&[11..15]));
+        assert_eq!(set(&[0..100]).intersection(&set(&[20..30])), set(&[20..30]));
+        assert_eq!(set(&[0..10]).intersection(&set(&[20..30])), set(&[]));
+        // adjacent ranges share no values
+        assert_eq!(set(&[0..10]).intersection(&set(&[10..20])), set(&[]));
+    }
+
+    #[test]
+    fn test_split_at() {
+        let (below, above) = set(&[0..10, 20..30]).split_at(25);
+        assert_eq!(below, set(&[0..10, 20..25]));
+        assert_eq!(above, set(&[25..30]));
+        assert_eq!(set(&[0..10]).split_at(-5), (set(&[]), set(&[0..10])));
+    }
+
+    #[test]
+    fn test_total_length() {
+        assert_eq!(set(&[0..10, 5..15, 20..21]).total_length(), 16);
+    }
+
+    #[test]
+    fn test_map_insert_overwrites_and_merges() {
+        let mut m: IntervalMap<i32, char> = [(0..10, 'a'), (10..20, 'b')].into_iter().collect();
+        m.insert(5..15, 'c');
+        assert_eq!(m.iter().map(|(r, v)| (r.clone(), *v)).collect::<Vec<_>>(), vec![(0..5, 'a'), (5..15, 'c'), (15..20, 'b')]);
+
+        m.insert(15..20, 'c');
+        m.insert(0..5, 'c');
+        assert_eq!(m.iter().map(|(r, v)| (r.clone(), *v)).collect::<Vec<_>>(), vec![(0..20, 'c')]);
+    }
+
+    #[test]
+    fn test_map_overlapping_and_gaps() {
+        let m: IntervalMap<i32, char> = [(0..10, 'a'), (20..30, 'b')].into_iter().collect();
+        assert_eq!(m.overlapping(5..25).collect::<Vec<_>>(), vec![(5..10, &'a'), (20..25, &'b')]);
+        assert_eq!(m.gaps(-5..35), set(&[-5..0, 10..20, 30..35]));
+        assert_eq!(m.get(&9), Some(&'a'));
+        assert_eq!(m.get(&10), None);
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<Range<i32>>> {
+        prop::collection::vec((-50..50, 0..20).prop_map(|(start, len)| start..start + len), 0..8)
+    }
+
+    fn values(ranges: &[Range<i32>]) -> Vec<i32> {
+        (-60..80).filter(|v| ranges.iter().any(|r| r.contains(v))).collect()
+    }
+
+    fn set_values(set: &IntervalSet<i32>) -> Vec<i32> {
+        (-60..80).filter(|v| set.contains(v)).collect()
+    }
+
+    fn is_canonical(set: &IntervalSet<i32>) -> bool {
+        set.ranges.iter().all(|r| !r.is_empty())
+            && set.ranges.windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_set_holds_inserted_values(a in ranges()) {
+            let s = set(&a);
+            prop_assert!(is_canonical(&s));
+            prop_assert_eq!(set_values(&s), values(&a));
+            prop_assert_eq!(s.total_length() as usize, values(&a).len());
+        }
+
+        #[test]
+        fn prop_set_operations(a in ranges(), b in ranges()) {
+            let (sa, sb) = (set(&a), set(&b));
+            let (va, vb) = (values(&a), values(&b));
+
+            let union = sa.union(&sb);
+            let intersection = sa.intersection(&sb);
+            let difference = sa.difference(&sb);
+            prop_assert!(is_canonical(&union) && is_canonical(&intersection) && is_canonical(&difference));
+
+            prop_assert_eq!(set_values(&union), (-60..80).filter(|v| va.contains(v) || vb.contains(v)).collect::<Vec<_>>());
+            prop_assert_eq!(set_values(&intersection), va.iter().copied().filter(|v| vb.contains(v)).collect::<Vec<_>>());
+            prop_assert_eq!(set_values(&difference), va.iter().copied().filter(|v| !vb.contains(v)).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn prop_split_at(a in ranges(), at in -60..80) {
+            let (below, above) = set(&a).split_at(at);
+            prop_assert!(is_canonical(&below) && is_canonical(&above));
+            prop_assert_eq!(set_values(&below), values(&a).into_iter().filter(|v| *v < at).collect::<Vec<_>>());
+            prop_assert_eq!(set_values(&above), values(&a).into_iter().filter(|v| *v >= at).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn prop_map_last_insert_wins(a in ranges()) {
+            // map each range to its position, so later ranges overwrite earlier ones
+            let m: IntervalMap<i32, usize> = a.iter().cloned().enumerate().map(|(i, r)| (r, i)).collect();
+            prop_assert!(m.entries.windows(2).all(|w| w[0].0.end <= w[1].0.start
+                && (w[0].0.end < w[1].0.start || w[0].1 != w[1].1)));
+
+            for v in -60..80 {
+                prop_assert_eq!(m.get(&v).copied(), a.iter().rposition(|r| r.contains(&v)));
+            }
+            prop_assert_eq!(m.domain(), set(&a));
+        }
+    }
+}
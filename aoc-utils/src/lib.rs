@@ -1,6 +1,12 @@
+pub mod cycle;
 pub mod error;
+pub mod geom;
+pub mod grid;
 pub mod input;
+pub mod interval;
+pub mod math;
 pub mod prelude;
-pub mod rangeutils;
+pub mod search;
 pub mod result;
+pub mod solution;
 pub mod timer;
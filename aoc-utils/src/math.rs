@@ -0,0 +1,284 @@
+use std::fmt::{self,Debug};
+use std::ops::{Add,Div,Mul,Neg,Rem,Sub};
+
+/// The primitive integer types, so the functions here can be generic over them
+pub trait Integer: Copy + Debug + Ord
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    /// The unsigned type of the same size, which can hold the absolute value
+    /// of any `Self`, even `MIN`
+    type Magnitude: Integer;
+
+    /// The absolute value, which never overflows
+    fn unsigned_abs(self) -> Self::Magnitude;
+
+    /// Converts an absolute value back, or `None` if it is too big for `Self`
+    fn from_magnitude(magnitude: Self::Magnitude) -> Option<Self>;
+
+    /// The remainder, always between zero and `|rhs|`
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+/// The signed primitive integer types
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($($t: ty => $m: ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                type Magnitude = $m;
+
+                fn unsigned_abs(self) -> $m {
+                    self.abs_diff(0)
+                }
+
+                fn from_magnitude(magnitude: $m) -> Option<Self> {
+                    <$t>::try_from(magnitude).ok()
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize);
+impl_integer!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/// Greatest common divisor of the absolute values, which can't overflow
+fn gcd_magnitude<T: Integer>(a: T, b: T) -> T::Magnitude {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != T::Magnitude::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Greatest common divisor, which is never negative, or `None` if it is too
+/// big for `T`. That only happens when both numbers are zero or `T::MIN`
+/// and at least one is `T::MIN`.
+pub fn checked_gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    T::from_magnitude(gcd_magnitude(a, b))
+}
+
+/// Greatest common divisor, which is never negative. `gcd(0, 0)` is zero.
+///
+/// # Panics
+///
+/// If the gcd is too big for `T`, which is only `gcd(T::MIN, 0)` and
+/// `gcd(T::MIN, T::MIN)` (see [`checked_gcd`]).
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("gcd should fit in the type of its arguments")
+}
+
+/// Least common multiple, which is never negative, or `None` if it overflows
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    let lcm = (a.unsigned_abs() / gcd_magnitude(a, b)).checked_mul(b.unsigned_abs())?;
+    T::from_magnitude(lcm)
+}
+
+/// Least common multiple of all of `values`, or `None` if it overflows
+pub fn lcm_all<T: Integer, I: IntoIterator<Item = T>>(values: I) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, lcm)
+}
+
+/// Extended Euclidean algorithm. Returns `(g, x, y)` where `g` is the gcd of
+/// `a` and `b` and `a * x + b * y == g`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let (mut old_t, mut t) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_s, -old_t)
+    }
+    else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, or `None` if `a` and `m` aren't
+/// coprime
+pub fn mod_inverse<T: Signed>(a: T, m: T) -> Option<T> {
+    let m = T::from_magnitude(m.unsigned_abs())?;
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == T::ONE).then(|| x.rem_euclid(m))
+}
+
+/// Chinese Remainder Theorem. Given congruences `x ≡ residue (mod modulus)`,
+/// finds the smallest non-negative `x` satisfying all of them and returns it
+/// along with the lcm of the moduli (every solution is `x` plus a multiple of
+/// it). The moduli don't need to be coprime. Returns `None` if there is no
+/// solution or a number involved doesn't fit in `T` (see [`checked_crt`]).
+pub fn crt<T: Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    checked_crt(congruences).ok().flatten()
+}
+
+/// A number was too big for the type it had to fit in
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "number too big for its type")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Like [`crt`], but tells the two ways of failing apart: `Ok(None)` if there
+/// is no solution, and `Err(Overflow)` if a number involved doesn't fit in
+/// `T`.
+pub fn checked_crt<T: Signed>(congruences: &[(T, T)]) -> Result<Option<(T, T)>, Overflow> {
+    let (mut x, mut m) = (T::ZERO, T::ONE);
+
+    for &(residue, modulus) in congruences {
+        let modulus = T::from_magnitude(modulus.unsigned_abs()).ok_or(Overflow)?;
+        if modulus == T::ZERO {
+            return Ok(None);
+        }
+
+        // x + m * k ≡ residue (mod modulus), so solve m * k ≡ residue - x
+        let g = checked_gcd(m, modulus).ok_or(Overflow)?;
+        let difference = (residue.rem_euclid(modulus) - x.rem_euclid(modulus)).rem_euclid(modulus);
+        if difference % g != T::ZERO {
+            return Ok(None);
+        }
+
+        // m / g and reduced are coprime, so there is always an inverse
+        let reduced = modulus / g;
+        let inverse = mod_inverse(m / g, reduced).ok_or(Overflow)?;
+        let k = (difference / g).checked_mul(inverse).ok_or(Overflow)?.rem_euclid(reduced);
+
+        let combined = (m / g).checked_mul(modulus).ok_or(Overflow)?;
+        x = m.checked_mul(k)
+            .and_then(|mk| mk.checked_add(x))
+            .ok_or(Overflow)?
+            .rem_euclid(combined);
+        m = combined;
+    }
+
+    Ok(Some((x, m)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u8, 7), 7);
+        assert_eq!(gcd(0i8, 0), 0);
+        assert_eq!(gcd(i64::MIN, 6), 2);
+        assert_eq!(gcd(i64::MIN, -1), 1);
+        assert_eq!(checked_gcd(i64::MIN, 0), None);
+        assert_eq!(checked_gcd(i64::MIN, i64::MIN), None);
+        assert_eq!(checked_gcd(i64::MIN, i64::MIN / 2), Some(1 << 62));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gcd_too_big() {
+        gcd(i8::MIN, 0);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4u64, 6), Some(12));
+        assert_eq!(lcm(-4i32, 6), Some(12));
+        assert_eq!(lcm(0u64, 6), Some(0));
+        // a * b overflows, but the lcm doesn't
+        assert_eq!(lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(lcm(u64::MAX, 2), None);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm(i64::MIN, 2), None);
+        assert_eq!(lcm(i64::MIN / 2, 2), Some(1 << 62));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240i64, 46), (-240, 46), (7, 0), (0, 7), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3i32, 11), Some(4));
+        assert_eq!(mod_inverse(-3i32, 11), Some(7));
+        assert_eq!(mod_inverse(6i32, 9), None);
+    }
+
+    #[test]
+    fn test_crt_coprime() {
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    }
+
+    #[test]
+    fn test_crt_not_coprime() {
+        assert_eq!(crt(&[(2i64, 6), (4, 8)]), Some((20, 24)));
+        assert_eq!(crt(&[(1i64, 6), (4, 8)]), None);
+        assert_eq!(crt(&[(-1i64, 4), (5, 6)]), Some((11, 12)));
+    }
+
+    #[test]
+    fn test_crt_overflow() {
+        assert_eq!(checked_crt(&[(1i8, 11), (2, 13)]), Err(Overflow));
+        assert_eq!(checked_crt(&[(1i8, 6), (4, 8)]), Ok(None));
+        assert_eq!(checked_crt(&[(1i8, 7), (2, 11)]), Ok(Some((57, 77))));
+        assert_eq!(crt(&[(1i8, 11), (2, 13)]), None);
+    }
+
+    #[test]
+    fn test_crt_brute_force() {
+        for a in 0..6i32 {
+            for b in 0..10 {
+                let expected = (0..30).find(|x| x % 6 == a && x % 10 == b);
+                assert_eq!(crt(&[(a, 6), (b, 10)]).map(|(x, _)| x), expected);
+            }
+        }
+    }
+}
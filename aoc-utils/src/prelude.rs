@@ -1,4 +1,5 @@
-pub use crate::error::{PuzzleError,PuzzleErrorKind};
-pub use crate::input::get_puzzle_input;
+pub use crate::error::{ErrorContext,Location,PuzzleError,PuzzleErrorKind};
+pub use crate::input::{InputSource,PuzzleArgs,PuzzleOption,PuzzlePart};
 pub use crate::result::PuzzleResult;
+pub use crate::solution::{run_main,Solution};
 pub use crate::timer::Timer;
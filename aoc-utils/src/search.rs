@@ -0,0 +1,205 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap,HashMap,VecDeque};
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cheapest way found to reach a goal
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct SearchResult<S, C> {
+    pub cost: C,
+    /// Every state visited, from the start to the goal inclusive
+    pub path: Vec<S>
+}
+
+/// Every state seen by a search, numbered in the order they were found, along
+/// with the state each one was reached from
+struct Visited<S> {
+    states: Vec<S>,
+    parents: Vec<Option<usize>>,
+    index: HashMap<S, usize>
+}
+
+impl<S> Visited<S> where S: Clone + Hash + Eq {
+    fn new(start: S) -> Self {
+        Self {
+            states: vec![start.clone()],
+            parents: vec![None],
+            index: HashMap::from([(start, 0)])
+        }
+    }
+
+    /// Numbers `state`, returning its number and whether it is new
+    fn insert(&mut self, state: S, parent: usize) -> (usize, bool) {
+        match self.index.entry(state) {
+            Entry::Occupied(entry) => (*entry.get(), false),
+            Entry::Vacant(entry) => {
+                let id = self.states.len();
+                self.states.push(entry.key().clone());
+                self.parents.push(Some(parent));
+                entry.insert(id);
+                (id, true)
+            }
+        }
+    }
+
+    /// Follows the parents back from `id` to the start
+    fn path(&self, mut id: usize) -> Vec<S> {
+        let mut path = vec![self.states[id].clone()];
+        while let Some(parent) = self.parents[id] {
+            path.push(self.states[parent].clone());
+            id = parent;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Finds the cheapest path from `start` to a state satisfying `is_goal`.
+/// `successors` gives the states reachable from a state along with the cost of
+/// each step, which must not be negative.
+pub fn dijkstra<S, C, FN, IN, FG>(start: S, successors: FN, is_goal: FG) -> Option<SearchResult<S, C>>
+    where S: Clone + Hash + Eq,
+          C: Copy + Ord + Default + Add<Output = C>,
+          FN: FnMut(&S) -> IN,
+          IN: IntoIterator<Item = (S, C)>,
+          FG: FnMut(&S) -> bool
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but explores the states that `heuristic` estimates to be
+/// closest to the goal first. The heuristic must never overestimate the
+/// remaining cost, otherwise the path found may not be the cheapest.
+pub fn astar<S, C, FN, IN, FH, FG>(start: S, mut successors: FN, mut heuristic: FH, mut is_goal: FG) -> Option<SearchResult<S, C>>
+    where S: Clone + Hash + Eq,
+          C: Copy + Ord + Default + Add<Output = C>,
+          FN: FnMut(&S) -> IN,
+          IN: IntoIterator<Item = (S, C)>,
+          FH: FnMut(&S) -> C,
+          FG: FnMut(&S) -> bool
+{
+    let mut costs = vec![C::default()];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut visited = Visited::new(start);
+
+    while let Some(Reverse((_, cost, id))) = queue.pop() {
+        if cost > costs[id] {
+            // already reached this state more cheaply
+            continue;
+        }
+
+        if is_goal(&visited.states[id]) {
+            return Some(SearchResult { cost, path: visited.path(id) });
+        }
+
+        for (next, step_cost) in successors(&visited.states[id]) {
+            let next_cost = cost + step_cost;
+            let estimate = heuristic(&next);
+            let (next_id, new) = visited.insert(next, id);
+
+            if new {
+                costs.push(next_cost);
+            }
+            else if next_cost < costs[next_id] {
+                costs[next_id] = next_cost;
+                visited.parents[next_id] = Some(id);
+            }
+            else {
+                continue;
+            }
+
+            queue.push(Reverse((next_cost + estimate, next_cost, next_id)));
+        }
+    }
+
+    None
+}
+
+/// Finds the path from `start` to a state satisfying `is_goal` with the fewest
+/// steps. The cost is the number of steps taken.
+pub fn bfs<S, FN, IN, FG>(start: S, mut successors: FN, mut is_goal: FG) -> Option<SearchResult<S, usize>>
+    where S: Clone + Hash + Eq,
+          FN: FnMut(&S) -> IN,
+          IN: IntoIterator<Item = S>,
+          FG: FnMut(&S) -> bool
+{
+    let mut queue = VecDeque::from([(0, 0)]);
+    let mut visited = Visited::new(start);
+
+    while let Some((steps, id)) = queue.pop_front() {
+        if is_goal(&visited.states[id]) {
+            return Some(SearchResult { cost: steps, path: visited.path(id) });
+        }
+
+        for next in successors(&visited.states[id]) {
+            if let (next_id, true) = visited.insert(next, id) {
+                queue.push_back((steps + 1, next_id));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a -1-> b -1-> c -1-> d, with a more expensive shortcut a -5-> d
+    fn edges(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('d', 5), ('b', 1)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1)],
+            _ => vec![]
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let result = dijkstra('a', edges, |n| *n == 'd').expect("Should find a path");
+        assert_eq!(result.cost, 3);
+        assert_eq!(result.path, vec!['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn test_dijkstra_start_is_goal() {
+        let result = dijkstra('a', edges, |n| *n == 'a').expect("Should find a path");
+        assert_eq!(result, SearchResult { cost: 0, path: vec!['a'] });
+    }
+
+    #[test]
+    fn test_no_path() {
+        assert!(dijkstra('b', edges, |n| *n == 'a').is_none());
+        assert!(bfs('b', |n| edges(n).into_iter().map(|(n, _)| n), |n| *n == 'a').is_none());
+    }
+
+    #[test]
+    fn test_bfs() {
+        let result = bfs('a', |n| edges(n).into_iter().map(|(n, _)| n), |n| *n == 'd').expect("Should find a path");
+        assert_eq!(result.cost, 1);
+        assert_eq!(result.path, vec!['a', 'd']);
+    }
+
+    #[test]
+    fn test_astar_grid() {
+        // walk around a wall on an open 5x5 grid
+        let wall = [(2, 0), (2, 1), (2, 2), (2, 3)];
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|(x, y)| (0..5).contains(x) && (0..5).contains(y) && !wall.contains(&(*x, *y)))
+                .map(|p| (p, 1))
+                .collect::<Vec<_>>()
+        };
+        let goal = (4, 0);
+
+        let result = astar((0, 0), successors, |(x, y)| (goal.0 - x).abs() + (goal.1 - y).abs(), |p| *p == goal)
+            .expect("Should find a path");
+        assert_eq!(result.cost, 12);
+        assert_eq!(result.path.len(), 13);
+        assert_eq!(dijkstra((0, 0), successors, |p| *p == goal).map(|r| r.cost), Some(12));
+    }
+}
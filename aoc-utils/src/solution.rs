@@ -0,0 +1,154 @@
+use std::fmt::Display;
+use std::io::Read;
+use std::time::{Duration,Instant};
+use crate::input::{PuzzleArgs,PuzzleOption,PuzzlePart};
+use crate::result::PuzzleResult;
+use crate::timer::Timer;
+
+/// A day's puzzle. The input is parsed once and then shared by both parts.
+pub trait Solution {
+    /// Day of the advent calendar that this solves
+    const DAY: u32;
+
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    /// Options of its own that the puzzle understands on the command line
+    const OPTIONS: &'static [PuzzleOption] = &[];
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input>;
+
+    /// Parses the input, taking account of any of [`Self::OPTIONS`] given on
+    /// the command line. By default they make no difference.
+    fn parse_with_args(input: &str, _args: &PuzzleArgs) -> PuzzleResult<Self::Input> {
+        Self::parse(input)
+    }
+    fn part1(input: &Self::Input) -> PuzzleResult<Self::Part1>;
+    fn part2(input: &Self::Input) -> PuzzleResult<Self::Part2>;
+}
+
+/// An answer to one part of a puzzle, along with how long it took
+#[derive(Debug)]
+pub struct Answer {
+    pub value: String,
+    pub elapsed: Duration
+}
+
+/// The results of running a [`Solution`] on some input
+#[derive(Debug)]
+pub struct SolutionReport {
+    pub day: u32,
+    pub parse_time: Duration,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>
+}
+
+impl SolutionReport {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time
+            + self.part1.as_ref().map(|a| a.elapsed).unwrap_or_default()
+            + self.part2.as_ref().map(|a| a.elapsed).unwrap_or_default()
+    }
+}
+
+fn timed<T, F>(f: F) -> PuzzleResult<Answer> where T: Display, F: FnOnce() -> PuzzleResult<T> {
+    let start = Instant::now();
+    let value = f()?.to_string();
+    Ok(Answer { value, elapsed: start.elapsed() })
+}
+
+/// Parses `input` and solves the selected part(s) of the puzzle
+pub fn run<S: Solution>(input: &str, part: PuzzlePart) -> PuzzleResult<SolutionReport> {
+    run_with_args::<S>(input, part, &PuzzleArgs::default())
+}
+
+/// Parses `input` with the options in `args` and solves the selected part(s)
+/// of the puzzle
+pub fn run_with_args<S: Solution>(input: &str, part: PuzzlePart, args: &PuzzleArgs) -> PuzzleResult<SolutionReport> {
+    let start = Instant::now();
+    let parsed = S::parse_with_args(input, args)?;
+    let parse_time = start.elapsed();
+
+    let part1 = if part.includes_part1() {
+        Some(timed(|| S::part1(&parsed))?)
+    }
+    else {
+        None
+    };
+
+    let part2 = if part.includes_part2() {
+        Some(timed(|| S::part2(&parsed))?)
+    }
+    else {
+        None
+    };
+
+    Ok(SolutionReport {
+        day: S::DAY,
+        parse_time,
+        part1,
+        part2
+    })
+}
+
+/// Runs a solution using the input, part and options given on the command
+/// line, falling back to the conventional input file for `crate_name` and to
+/// `default_part` if they weren't given.
+pub fn run_main<S: Solution>(crate_name: &str, default_part: PuzzlePart) -> PuzzleResult<()> {
+    let _timer = Timer::new();
+    let args = PuzzleArgs::from_env(S::OPTIONS)?;
+
+    let mut input = String::new();
+    args.input_source(crate_name)?.open()?.read_to_string(&mut input)?;
+
+    let report = run_with_args::<S>(&input, args.part(default_part), &args)?;
+
+    if let Some(answer) = report.part1 {
+        println!("Part 1: {}", answer.value);
+    }
+
+    if let Some(answer) = report.part2 {
+        println!("Part 2: {}", answer.value);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        const DAY: u32 = 0;
+
+        type Input = Vec<String>;
+        type Part1 = usize;
+        type Part2 = String;
+
+        fn parse(input: &str) -> PuzzleResult<Self::Input> {
+            Ok(input.lines().map(str::to_owned).collect())
+        }
+
+        fn part1(input: &Self::Input) -> PuzzleResult<Self::Part1> {
+            Ok(input.len())
+        }
+
+        fn part2(input: &Self::Input) -> PuzzleResult<Self::Part2> {
+            Ok(input.join("+"))
+        }
+    }
+
+    #[test]
+    fn test_run_selected_parts() {
+        let report = run::<LineCount>("a\nb\nc\n", PuzzlePart::One).expect("Should run");
+        assert_eq!(report.part1.map(|a| a.value), Some(String::from("3")));
+        assert!(report.part2.is_none());
+
+        let report = run::<LineCount>("a\nb\nc\n", PuzzlePart::Both).expect("Should run");
+        assert_eq!(report.part1.map(|a| a.value), Some(String::from("3")));
+        assert_eq!(report.part2.map(|a| a.value), Some(String::from("a+b+c")));
+    }
+}
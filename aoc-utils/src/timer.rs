@@ -13,6 +13,12 @@ impl Timer {
     }
 }
 
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for Timer {
     fn drop(&mut self) {
         println!("Execution time: {}µs", self.0.elapsed().as_micros())
@@ -0,0 +1,150 @@
+use std::env;
+use std::io::Read;
+use std::ops::RangeInclusive;
+use std::path::{Path,PathBuf};
+use std::process::ExitCode;
+use aoc_utils::input::{conventional_input_path,INPUTS_DIR};
+use aoc_utils::prelude::*;
+use aoc_utils::solution::{run,Answer,SolutionReport};
+
+type Runner = fn(&str, PuzzlePart) -> PuzzleResult<SolutionReport>;
+
+const SOLUTIONS: [Runner; 19] = [
+    run::<day1::Day1>,
+    run::<day2::Day2>,
+    run::<day3::Day3>,
+    run::<day4::Day4>,
+    run::<day5::Day5>,
+    run::<day6::Day6>,
+    run::<day7::Day7>,
+    run::<day8::Day8>,
+    run::<day9::Day9>,
+    run::<day10::Day10>,
+    run::<day11::Day11>,
+    run::<day12::Day12>,
+    run::<day13::Day13>,
+    run::<day14::Day14>,
+    run::<day15::Day15>,
+    run::<day16::Day16>,
+    run::<day17::Day17>,
+    run::<day18::Day18>,
+    run::<day19::Day19>,
+];
+
+/// Parses `7`, `1..19` (or `1..=19`) and `all` into the days to run
+fn parse_days(s: &str) -> PuzzleResult<RangeInclusive<usize>> {
+    let all = 1..=SOLUTIONS.len();
+
+    let days = if s == "all" {
+        all.clone()
+    }
+    else if let Some((first, last)) = s.split_once("..") {
+        let last = last.strip_prefix('=').unwrap_or(last);
+        first.parse::<usize>()?..=last.parse::<usize>()?
+    }
+    else {
+        let day = s.parse::<usize>()?;
+        day..=day
+    };
+
+    if days.is_empty() || !all.contains(days.start()) || !all.contains(days.end()) {
+        return Err(PuzzleErrorKind::InvalidArgument.into());
+    }
+
+    Ok(days)
+}
+
+struct RunnerArgs {
+    days: RangeInclusive<usize>,
+    part: PuzzlePart,
+    inputs: PathBuf
+}
+
+impl RunnerArgs {
+    fn from_env() -> PuzzleResult<Self> {
+        let mut days = None;
+        let mut part = PuzzlePart::Both;
+        let mut inputs = PathBuf::from(INPUTS_DIR);
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => part = args.next().ok_or(PuzzleErrorKind::InvalidArgument)?.parse()?,
+                "--inputs" => inputs = args.next().ok_or(PuzzleErrorKind::InvalidArgument)?.into(),
+                _ if days.is_none() => days = Some(parse_days(&arg)?),
+                _ => return Err(PuzzleErrorKind::InvalidArgument.into())
+            }
+        }
+
+        Ok(Self {
+            days: days.ok_or(PuzzleErrorKind::InvalidArgument)?,
+            part,
+            inputs
+        })
+    }
+}
+
+fn run_day(day: usize, part: PuzzlePart, inputs: &Path) -> PuzzleResult<SolutionReport> {
+    let source = InputSource::Path(conventional_input_path(inputs, &format!("day{}", day))?);
+
+    let mut input = String::new();
+    source.open()?.read_to_string(&mut input)?;
+
+    SOLUTIONS[day - 1](&input, part)
+}
+
+fn format_answer(answer: &Option<Answer>) -> String {
+    answer
+        .as_ref()
+        .map(|a| a.value.clone())
+        .unwrap_or_else(|| String::from("-"))
+}
+
+/// Prints a table of every day's answers. A day that fails gets one line in
+/// the table and its full error on stderr, and makes the exit status a
+/// failure.
+fn main() -> PuzzleResult<ExitCode> {
+    let _timer = Timer::new();
+    let args = RunnerArgs::from_env()?;
+    let mut failed = false;
+
+    println!("{:>3}  {:<20}  {:<20}  {:>10}", "Day", "Part 1", "Part 2", "Time");
+
+    for day in args.days {
+        match run_day(day, args.part, &args.inputs) {
+            Ok(report) => println!("{:>3}  {:<20}  {:<20}  {:>8}µs",
+                report.day,
+                format_answer(&report.part1),
+                format_answer(&report.part2),
+                report.total_time().as_micros()),
+            Err(e) => {
+                failed = true;
+                println!("{:>3}  failed: {}", day, e.summary());
+                eprintln!("Day {}: {:?}", day, e);
+            }
+        }
+    }
+
+    Ok(if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("7").expect("Should parse"), 7..=7);
+        assert_eq!(parse_days("1..19").expect("Should parse"), 1..=19);
+        assert_eq!(parse_days("3..=5").expect("Should parse"), 3..=5);
+        assert_eq!(parse_days("all").expect("Should parse"), 1..=19);
+    }
+
+    #[test]
+    fn test_parse_invalid_days() {
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("20").is_err());
+        assert!(parse_days("5..3").is_err());
+        assert!(parse_days("seven").is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+use std::fs;
+use std::io::BufRead;
+use aoc_utils::prelude::*;
+
+pub mod scanner;
+pub mod vocabulary;
+
+use scanner::Scanner;
+use vocabulary::Vocabulary;
+
+/// What to do with a calibration line that has no tokens in it
+#[derive(Clone,Copy,Debug,Default,Eq,PartialEq)]
+pub enum Mode {
+    /// Give up on the whole document
+    #[default]
+    Strict,
+    /// Skip the line, but remember it
+    Lenient
+}
+
+/// A calibration line that was skipped as it has no tokens in it
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct InvalidLine {
+    pub line_number: usize,
+    pub content: String
+}
+
+impl fmt::Display for InvalidLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: no digits in `{}`", self.line_number, self.content)
+    }
+}
+
+/// The total of a calibration document, and the lines that didn't count
+/// towards it
+#[derive(Clone,Debug,Default,Eq,PartialEq)]
+pub struct Calibration {
+    pub total: u64,
+    pub skipped: Vec<InvalidLine>
+}
+
+/// Shows the total, and how many lines were skipped if there were any
+impl fmt::Display for Calibration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.total)?;
+
+        if !self.skipped.is_empty() {
+            write!(f, " (skipped {} invalid line(s))", self.skipped.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// What to do with invalid lines, the vocabulary to use instead of digits
+/// and words for part 2, and whether its tokens match regardless of case
+#[derive(Clone,Debug,Default,Eq,PartialEq)]
+pub struct Settings {
+    pub mode: Mode,
+    pub vocabulary: Option<Vocabulary>,
+    pub case_insensitive: bool
+}
+
+impl Settings {
+    /// Reads `--lenient`, `--vocabulary PATH` and `--case-insensitive`
+    pub fn from_args(args: &PuzzleArgs) -> PuzzleResult<Self> {
+        let mode = if args.flag("lenient") {
+            Mode::Lenient
+        }
+        else {
+            Mode::Strict
+        };
+
+        let vocabulary = args
+            .value("vocabulary")
+            .map(load_vocabulary)
+            .transpose()?;
+
+        Ok(Settings { mode, vocabulary, case_insensitive: args.flag("case-insensitive") })
+    }
+
+    pub fn part1_vocabulary(&self) -> Vocabulary {
+        Vocabulary::digits()
+    }
+
+    pub fn part2_vocabulary(&self) -> Vocabulary {
+        self.vocabulary
+            .clone()
+            .unwrap_or_else(Vocabulary::digits_and_words)
+            .case_insensitive(self.case_insensitive)
+    }
+}
+
+/// A calibration document and how to read it
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct Document {
+    pub text: String,
+    pub settings: Settings
+}
+
+/// Reads a vocabulary file (see [`Vocabulary`]'s `FromStr`)
+pub fn load_vocabulary(path: &str) -> PuzzleResult<Vocabulary> {
+    fs::read_to_string(path)
+        .map_err(|e| PuzzleErrorKind::InputFileError(path.into(), e))?
+        .parse::<Vocabulary>()
+        .map_err(|e| PuzzleError::new(PuzzleErrorKind::InvalidArgument, format!("cannot load vocabulary {}", path)).with_source(e))
+}
+
+/// Adds up the calibration values of every line read from `reader`, one line
+/// at a time, so the document can be much bigger than memory
+pub fn calibration_total<R: BufRead>(reader: R, scanner: &mut Scanner, mode: Mode) -> PuzzleResult<Calibration> {
+    let mut calibrations = calibration_totals(reader, std::slice::from_mut(scanner), mode)?;
+    Ok(calibrations.remove(0))
+}
+
+/// Like [`calibration_total`], but adds up the values found by each of
+/// `scanners` in a single pass, so the document is only read once however
+/// many parts are solved
+pub fn calibration_totals<R: BufRead>(mut reader: R, scanners: &mut [Scanner], mode: Mode) -> PuzzleResult<Vec<Calibration>> {
+    let mut calibrations = vec![Calibration::default(); scanners.len()];
+    let mut line = Vec::new();
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        if line.trim_ascii().is_empty() {
+            continue;
+        }
+
+        for (scanner, calibration) in scanners.iter_mut().zip(calibrations.iter_mut()) {
+            match (scanner.calibration_value(&line), mode) {
+                (Some(code), _) => calibration.total = calibration.total
+                    .checked_add(code)
+                    .ok_or(PuzzleError::new(PuzzleErrorKind::InputError, "calibration total is too big"))
+                    .at_line(line_number, String::from_utf8_lossy(line.trim_ascii_end()).as_ref())?,
+                (None, Mode::Lenient) => calibration.skipped.push(InvalidLine {
+                    line_number,
+                    content: String::from_utf8_lossy(line.trim_ascii_end()).into_owned()
+                }),
+                (None, Mode::Strict) => return Err(PuzzleError::new(PuzzleErrorKind::InputError, "no digits in calibration line"))
+                    .at_line(line_number, String::from_utf8_lossy(line.trim_ascii_end()).as_ref())
+            }
+        }
+    }
+
+    Ok(calibrations)
+}
+
+/// Solves a puzzle part with the tokens in `vocabulary`
+fn solve(document: &Document, vocabulary: Vocabulary) -> PuzzleResult<Calibration> {
+    calibration_total(document.text.as_bytes(), &mut vocabulary.scanner()?, document.settings.mode)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+
+    type Input = Document;
+    type Part1 = Calibration;
+    type Part2 = Calibration;
+
+    /// `--lenient` skips lines with no digits instead of failing,
+    /// `--vocabulary PATH` loads the tokens for part 2 from a file and
+    /// `--case-insensitive` matches them regardless of case
+    const OPTIONS: &'static [PuzzleOption] = &[
+        PuzzleOption::Flag("lenient"),
+        PuzzleOption::Value("vocabulary"),
+        PuzzleOption::Flag("case-insensitive")
+    ];
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        Ok(Document { text: input.to_owned(), settings: Settings::default() })
+    }
+
+    fn parse_with_args(input: &str, args: &PuzzleArgs) -> PuzzleResult<Self::Input> {
+        Ok(Document { text: input.to_owned(), settings: Settings::from_args(args)? })
+    }
+
+    fn part1(input: &Self::Input) -> PuzzleResult<Self::Part1> {
+        solve(input, input.settings.part1_vocabulary())
+    }
+
+    fn part2(input: &Self::Input) -> PuzzleResult<Self::Part2> {
+        solve(input, input.settings.part2_vocabulary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = "1abc2\nnothing here\n\na1b2c3d4e5f\nxyz\n";
+
+    #[test]
+    fn test_lenient_skips_invalid_lines() {
+        let mut scanner = Vocabulary::digits().scanner().expect("Should build");
+        let calibration = calibration_total(DOCUMENT.as_bytes(), &mut scanner, Mode::Lenient).expect("Should add up");
+
+        assert_eq!(calibration.total, 12 + 15);
+        assert_eq!(calibration.skipped, vec![
+            InvalidLine { line_number: 2, content: String::from("nothing here") },
+            InvalidLine { line_number: 5, content: String::from("xyz") }
+        ]);
+    }
+
+    #[test]
+    fn test_strict_stops_at_invalid_line() {
+        let mut scanner = Vocabulary::digits().scanner().expect("Should build");
+        let error = calibration_total(DOCUMENT.as_bytes(), &mut scanner, Mode::Strict).expect_err("Should fail");
+
+        assert_eq!(error.location().map(|l| l.line), Some(2));
+    }
+
+    #[test]
+    fn test_both_parts_in_one_pass() {
+        let mut scanners = [
+            Vocabulary::digits().scanner().expect("Should build"),
+            Vocabulary::digits_and_words().scanner().expect("Should build")
+        ];
+        let calibrations = calibration_totals("two1nine\nabcone\n".as_bytes(), &mut scanners, Mode::Lenient).expect("Should add up");
+
+        assert_eq!(calibrations[0].total, 11);
+        assert_eq!(calibrations[0].skipped.len(), 1);
+        assert_eq!(calibrations[1].total, 29 + 11);
+        assert!(calibrations[1].skipped.is_empty());
+    }
+
+    #[test]
+    fn test_loaded_vocabulary() {
+        let path = std::env::temp_dir().join("day1-test-vocabulary.txt");
+        fs::write(&path, "uno = 1\ndos = 2\nmillion = 1000000\n").expect("Should write");
+        let path = path.to_string_lossy().into_owned();
+
+        let args = PuzzleArgs::parse([String::from("--vocabulary"), path.clone()], Day1::OPTIONS).expect("Should parse");
+        let document = Day1::parse_with_args("unodos\nmillion\n", &args).expect("Should load");
+        assert_eq!(Day1::part2(&document).expect("Should add up").total, 12 + 11_000_000);
+
+        fs::write(&path, "uno 1\n").expect("Should write");
+        assert!(Day1::parse_with_args("", &args).is_err());
+        fs::remove_file(&path).expect("Should remove");
+    }
+
+    #[test]
+    fn test_case_insensitive_flag() {
+        let path = std::env::temp_dir().join("day1-test-case-insensitive.txt");
+        fs::write(&path, "zéro = 0\nun = 1\n").expect("Should write");
+        let path = path.to_string_lossy().into_owned();
+
+        let args = PuzzleArgs::parse([String::from("--vocabulary"), path.clone()], Day1::OPTIONS).expect("Should parse");
+        let document = Day1::parse_with_args("UNxZÉRO\n", &args).expect("Should load");
+        assert!(Day1::part2(&document).is_err());
+
+        let args = PuzzleArgs::parse([String::from("--vocabulary"), path.clone(), String::from("--case-insensitive")], Day1::OPTIONS)
+            .expect("Should parse");
+        let document = Day1::parse_with_args("UNxZÉRO\n", &args).expect("Should load");
+        assert_eq!(Day1::part2(&document).expect("Should add up").total, 10);
+        fs::remove_file(&path).expect("Should remove");
+    }
+
+    #[test]
+    fn test_lenient_flag() {
+        let args = PuzzleArgs::parse([String::from("--lenient")], Day1::OPTIONS).expect("Should parse");
+        let document = Day1::parse_with_args(DOCUMENT, &args).expect("Should parse");
+        let calibration = Day1::part1(&document).expect("Should add up");
+
+        assert_eq!(calibration.to_string(), "27 (skipped 2 invalid line(s))");
+        assert!(Day1::part1(&Day1::parse(DOCUMENT).expect("Should parse")).is_err());
+    }
+}
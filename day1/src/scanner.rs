@@ -0,0 +1,131 @@
+use aho_corasick::{AhoCorasick,AhoCorasickBuilder,MatchKind};
+use aoc_utils::prelude::*;
+
+/// Finds the first and last tokens of a [`Vocabulary`](crate::vocabulary::Vocabulary)
+/// in calibration lines, without looking at the matches in between
+pub struct Scanner {
+    forward: AhoCorasick,
+    /// Matches the tokens spelt backwards, to search lines from the end
+    backward: AhoCorasick,
+    /// The value of each token, in the same order as the automatons' patterns
+    values: Vec<u32>,
+    /// Whether lines are lowercased before they are searched, as the tokens
+    /// were
+    case_insensitive: bool,
+    /// Reused to hold each line backwards
+    reversed: Vec<u8>,
+    /// Reused to hold each line lowercased
+    folded: String
+}
+
+impl Scanner {
+    pub(crate) fn new(tokens: &[(String, u32)], case_insensitive: bool) -> PuzzleResult<Self> {
+        let build = |patterns: Vec<Vec<u8>>| AhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(patterns)
+            .map_err(|e| PuzzleError::new(PuzzleErrorKind::InvalidArgument, "cannot build a matcher for the vocabulary").with_source(e));
+
+        let patterns: Vec<String> = tokens
+            .iter()
+            .map(|(t, _)| if case_insensitive { t.to_lowercase() } else { t.clone() })
+            .collect();
+
+        let forward = build(patterns.iter().map(|t| t.as_bytes().to_vec()).collect())?;
+        let backward = build(patterns.iter().map(|t| t.bytes().rev().collect()).collect())?;
+
+        Ok(Self {
+            forward,
+            backward,
+            values: tokens.iter().map(|(_, v)| *v).collect(),
+            case_insensitive,
+            reversed: Vec::new(),
+            folded: String::new()
+        })
+    }
+
+    /// The values of the token that starts first in `line` and the token that
+    /// ends last, which may overlap. Where several tokens start (or end) in
+    /// the same place, the longest wins.
+    pub fn first_and_last(&mut self, line: &[u8]) -> Option<(u32, u32)> {
+        if !self.case_insensitive {
+            return self.find(line);
+        }
+
+        let mut folded = std::mem::take(&mut self.folded);
+        folded.clear();
+        for c in String::from_utf8_lossy(line).chars() {
+            folded.extend(c.to_lowercase());
+        }
+
+        let found = self.find(folded.as_bytes());
+        self.folded = folded;
+        found
+    }
+
+    fn find(&mut self, line: &[u8]) -> Option<(u32, u32)> {
+        let first = self.forward.find(line)?;
+
+        self.reversed.clear();
+        self.reversed.extend(line.iter().rev());
+        let last = self.backward
+            .find(&self.reversed)
+            .expect("a line with a token forwards has one backwards");
+
+        Some((self.values[first.pattern()], self.values[last.pattern()]))
+    }
+
+    /// The first value followed by the last value, i.e. `first * 10 + last`,
+    /// which is worked out as a `u64` so that even the biggest values fit
+    pub fn calibration_value(&mut self, line: &[u8]) -> Option<u64> {
+        self.first_and_last(line).map(|(first, last)| u64::from(first) * 10 + u64::from(last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn test_overlapping_words() {
+        let mut scanner = Vocabulary::digits_and_words().scanner().expect("Should build");
+        assert_eq!(scanner.calibration_value(b"xtwone3four"), Some(24));
+        assert_eq!(scanner.calibration_value(b"eightwo"), Some(82));
+        assert_eq!(scanner.calibration_value(b"oneight"), Some(18));
+        assert_eq!(scanner.calibration_value(b"abc"), None);
+    }
+
+    #[test]
+    fn test_longest_token_wins() {
+        let mut scanner = [("seven", 7), ("seventeen", 17), ("teen", 10)]
+            .into_iter()
+            .collect::<Vocabulary>()
+            .scanner()
+            .expect("Should build");
+
+        assert_eq!(scanner.first_and_last(b"xseventeenx"), Some((17, 17)));
+        assert_eq!(scanner.first_and_last(b"sevenx"), Some((7, 7)));
+    }
+
+    #[test]
+    fn test_large_values() {
+        let mut scanner = [("big", u32::MAX)]
+            .into_iter()
+            .collect::<Vocabulary>()
+            .scanner()
+            .expect("Should build");
+
+        assert_eq!(scanner.calibration_value(b"big"), Some(u64::from(u32::MAX) * 11));
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        let vocabulary: Vocabulary = [("zéro", 0), ("ÉLF", 11)].into_iter().collect();
+
+        let mut scanner = vocabulary.clone().scanner().expect("Should build");
+        assert_eq!(scanner.first_and_last("ZÉROélf".as_bytes()), None);
+
+        let mut scanner = vocabulary.case_insensitive(true).scanner().expect("Should build");
+        assert_eq!(scanner.first_and_last("ZÉROxélf".as_bytes()), Some((0, 11)));
+        assert_eq!(scanner.first_and_last("zÉrO".as_bytes()), Some((0, 0)));
+    }
+}
@@ -0,0 +1,137 @@
+use std::str::FromStr;
+use aoc_utils::prelude::*;
+use crate::scanner::Scanner;
+
+const DIGITS: [(&str, u32); 9] = [
+    ("1", 1), ("2", 2), ("3", 3), ("4", 4), ("5", 5), ("6", 6), ("7", 7), ("8", 8), ("9", 9)
+];
+
+const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1), ("two", 2), ("three", 3), ("four", 4), ("five", 5), ("six", 6), ("seven", 7), ("eight", 8), ("nine", 9)
+];
+
+/// The tokens that can appear in a calibration document and the value each
+/// one stands for
+#[derive(Clone,Debug,Default,Eq,PartialEq)]
+pub struct Vocabulary {
+    tokens: Vec<(String, u32)>,
+    case_insensitive: bool
+}
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Just the digits 1 to 9
+    pub fn digits() -> Self {
+        DIGITS.into_iter().collect()
+    }
+
+    /// The digits 1 to 9 and the English words for them
+    pub fn digits_and_words() -> Self {
+        DIGITS.into_iter().chain(ENGLISH).collect()
+    }
+
+    /// Adds a token, replacing its value if it is already there
+    pub fn insert(&mut self, token: &str, value: u32) {
+        match self.tokens.iter_mut().find(|(t, _)| t == token) {
+            Some((_, v)) => *v = value,
+            None => self.tokens.push((token.to_owned(), value))
+        }
+    }
+
+    /// Whether tokens match regardless of case, which compares the tokens
+    /// and each line lowercased, so works for any script with case
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    pub fn tokens(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.tokens.iter().map(|(t, v)| (t.as_str(), *v))
+    }
+
+    /// Builds the scanner that finds the tokens in a line
+    pub fn scanner(&self) -> PuzzleResult<Scanner> {
+        if self.tokens.is_empty() {
+            return Err(PuzzleError::new(PuzzleErrorKind::InvalidArgument, "vocabulary has no tokens"));
+        }
+
+        Scanner::new(&self.tokens, self.case_insensitive)
+    }
+}
+
+impl<'a> FromIterator<(&'a str, u32)> for Vocabulary {
+    fn from_iter<I: IntoIterator<Item = (&'a str, u32)>>(iter: I) -> Self {
+        let mut vocabulary = Vocabulary::new();
+        for (token, value) in iter {
+            vocabulary.insert(token, value);
+        }
+        vocabulary
+    }
+}
+
+/// Reads a vocabulary with a `token = value` pair on each line. Blank lines
+/// and lines starting with `#` are skipped.
+impl FromStr for Vocabulary {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut vocabulary = Vocabulary::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let (token, value) = trimmed
+                .split_once('=')
+                .ok_or(PuzzleError::parse("expected `token = value`"))
+                .at_line(index + 1, line)?;
+
+            let (token, value) = (token.trim(), value.trim());
+            if token.is_empty() {
+                return Err(PuzzleError::parse("token is empty")).at_line(index + 1, line);
+            }
+
+            if vocabulary.tokens().any(|(t, _)| t == token) {
+                return Err(PuzzleError::parse(format!("`{}` is already in the vocabulary", token))).at_token(index + 1, line, token);
+            }
+
+            let value = value
+                .parse()
+                .context("value must be a whole number")
+                .at_token(index + 1, line, value)?;
+
+            vocabulary.insert(token, value);
+        }
+
+        Ok(vocabulary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loaded_vocabulary() {
+        let vocabulary: Vocabulary = "# French\nzéro = 0\nun = 1\ndeux=2\n\ndix = 10\n"
+            .parse()
+            .expect("Should parse");
+        let mut scanner = vocabulary.case_insensitive(true).scanner().expect("Should build");
+
+        assert_eq!(scanner.first_and_last("DEUXzéroun".as_bytes()), Some((2, 1)));
+        assert_eq!(scanner.first_and_last(b"Dix"), Some((10, 10)));
+    }
+
+    #[test]
+    fn test_bad_vocabulary() {
+        assert!("one 1".parse::<Vocabulary>().is_err());
+        assert!("one = x".parse::<Vocabulary>().is_err());
+        assert!("one = 1\none = 2".parse::<Vocabulary>().is_err());
+        assert!(Vocabulary::new().scanner().is_err());
+    }
+}
@@ -0,0 +1,28 @@
+use aoc_utils::prelude::*;
+
+pub mod board;
+pub mod tiletype;
+
+use board::Board;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = Board;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(board: &Self::Input) -> PuzzleResult<Self::Part1> {
+        board.find_furthest_distance()
+    }
+
+    fn part2(board: &Self::Input) -> PuzzleResult<Self::Part2> {
+        board.count_enclosed_spaces()
+    }
+}
@@ -43,7 +43,7 @@ impl TryFrom<char> for TileType {
             '7' => Ok(TileType::SW),
             'F' => Ok(TileType::SE),
             'S' => Ok(TileType::Start),
-            _ => Err(PuzzleErrorKind::ParseError.into())
+            c => Err(PuzzleError::parse(format!("unknown tile `{}`", c)))
         }
     }
 }
@@ -0,0 +1,197 @@
+use std::collections::BTreeSet;
+use aoc_utils::prelude::*;
+
+#[derive(Clone,Debug)]
+pub struct Galaxy {
+    x: i64,
+    y: i64,
+}
+
+impl Galaxy {
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn distance(&self, other: &Self) -> i64 {
+        let (fx, gx) = (self.x.min(other.x), self.x.max(other.x));
+        let (fy, gy) = (self.y.min(other.y), self.y.max(other.y));
+        let dx = gx - fx;
+        let dy = gy - fy;
+        dx + dy
+    }
+}
+
+#[derive(Clone,Debug,Default)]
+pub struct Universe {
+    galaxies: Vec<Galaxy>
+}
+
+/// Works out how far each coordinate moves once every empty coordinate before
+/// it has grown to `expansion` times its original size.
+fn expansion_offsets(filled: &BTreeSet<i64>, expansion: i64) -> Vec<i64> {
+    let maximum = filled.last().map_or(0, |last| last + 1);
+    let mut offsets: Vec<i64> = Vec::new();
+
+    let mut current_offset = 0;
+
+    for i in 0..maximum {
+        if !filled.contains(&i) {
+            current_offset += expansion - 1;
+        }
+        offsets.push(current_offset);
+    }
+
+    offsets
+}
+
+impl Universe {
+    pub fn new() -> Self {
+        Self { galaxies: Vec::new() }
+    }
+
+    pub fn expand_x(&mut self, filled_columns: &BTreeSet<i64>, expansion: i64) {
+        let offsets = expansion_offsets(filled_columns, expansion);
+
+        for g in &mut self.galaxies {
+            g.x += offsets[g.x as usize];
+        }
+    }
+
+    pub fn expand_y(&mut self, filled_rows: &BTreeSet<i64>, expansion: i64) {
+        let offsets = expansion_offsets(filled_rows, expansion);
+
+        for g in &mut self.galaxies {
+            g.y += offsets[g.y as usize];
+        }
+    }
+
+    pub fn total_distance(&self) -> i64 {
+        let galaxy_count = self.galaxies.len();
+
+        self.galaxies[..galaxy_count-1]
+            .iter()
+            .enumerate()
+            .flat_map(|(idx,g1)| self.galaxies[idx+1..galaxy_count]
+                .iter()
+                .map(|g2| g1.distance(g2))
+            )
+            .sum()
+    }
+}
+
+/// A universe along with which of its rows and columns contain galaxies
+pub struct Observation {
+    universe: Universe,
+    filled_rows: BTreeSet<i64>,
+    filled_columns: BTreeSet<i64>
+}
+
+impl Observation {
+    fn total_distance(&self, expansion: i64) -> i64 {
+        let mut expanded = self.universe.clone();
+        expanded.expand_x(&self.filled_columns, expansion);
+        expanded.expand_y(&self.filled_rows, expansion);
+        expanded.total_distance()
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input = Observation;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        let mut universe = Universe::new();
+        let mut filled_columns = BTreeSet::new();
+        let mut filled_rows = BTreeSet::new();
+
+        for (line_counter, line) in input.lines().enumerate() {
+            let new_galaxies = line
+                .chars()
+                .enumerate()
+                .filter_map(|(idx,ch)|
+                    if ch == '#' {
+                        Some(Galaxy::new(idx as i64, line_counter as i64))
+                    }
+                    else {
+                        None
+                    }
+                )
+                .collect::<Vec<Galaxy>>();
+
+            filled_columns.extend(new_galaxies
+                .iter()
+                .map(|g| g.x)
+            );
+
+            if !new_galaxies.is_empty() {
+                filled_rows.insert(line_counter as i64);
+            }
+
+            universe.galaxies.extend(new_galaxies);
+        }
+
+        Ok(Observation {
+            universe,
+            filled_rows,
+            filled_columns
+        })
+    }
+
+    fn part1(observation: &Self::Input) -> PuzzleResult<Self::Part1> {
+        Ok(observation.total_distance(2))
+    }
+
+    fn part2(observation: &Self::Input) -> PuzzleResult<Self::Part2> {
+        Ok(observation.total_distance(1_000_000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_1() {
+        let g1 = Galaxy::new(1, 6);
+        let g2 = Galaxy::new(5, 11);
+
+        let distance = g1.distance(&g2);
+
+        assert_eq!(distance, 9, "distance should be 9");
+    }
+
+    #[test]
+    fn test_distance_2() {
+        let g1 = Galaxy::new(4, 0);
+        let g2 = Galaxy::new(9, 10);
+
+        let distance = g1.distance(&g2);
+
+        assert_eq!(distance, 15, "distance should be 15");
+    }
+
+    #[test]
+    fn test_distance_3() {
+        let g1 = Galaxy::new(0, 11);
+        let g2 = Galaxy::new(5, 11);
+
+        let distance = g1.distance(&g2);
+
+        assert_eq!(distance, 5, "distance should be 5");
+    }
+
+    #[test]
+    fn test_distance_4() {
+        let g1 = Galaxy::new(5, 0);
+        let g2 = Galaxy::new(10, 1);
+
+        let distance = g1.distance(&g2);
+
+        assert_eq!(distance, 6, "distance should be 6");
+    }
+}
@@ -0,0 +1,169 @@
+use aoc_utils::prelude::*;
+
+#[memoize::memoize]
+fn recurse(springs: Vec<u8>, counts: Vec<u64>, current_run: u64) -> u64 {
+    if counts.is_empty() {
+        // this recursion is good as long as there are no more '#'
+        if current_run == 0 && springs.iter().all(|c| *c == b'.' || *c == b'?') {
+            return 1;
+        }
+        else {
+            return 0;
+        }
+    }
+
+    if current_run > counts[0] {
+        // this instantly ends a recursion
+        return 0;
+    }
+
+    if springs.is_empty() {
+        // we have counts left, but no more input, so for this recursion to be
+        // good, we need to make sure the current_run matches the one-and-only
+        // count left
+        if counts.len() == 1 && counts[0] == current_run {
+            return 1;
+        }
+        else {
+            return 0;
+        }
+    }
+
+    match springs[0] {
+        b'.' => {
+            if current_run > 0 {
+                if current_run == counts[0] {
+                    // so far so good, keep going with the input/counts
+                    recurse(springs[1..].to_vec(), counts[1..].to_vec(), 0)
+                }
+                else {
+                    // the run length is NG
+                    0
+                }
+            }
+            else {
+                // we're currently not in a run, just keep going
+                recurse(springs[1..].to_vec(), counts.clone(), 0)
+            }
+        },
+
+        b'#' => {
+            // if we find something that extends (or starts) the run, we keep
+            // looking through
+            recurse(springs[1..].to_vec(), counts.clone(), current_run + 1)
+        },
+
+        b'?' => {
+            if current_run > 0 {
+                // Handle the case where "?" could continue or end a run
+                if current_run == counts[0] {
+                    return recurse(springs[1..].to_vec(), counts[1..].to_vec(), 0);
+                }
+                
+                recurse(springs[1..].to_vec(), counts.clone(), current_run + 1)
+            }
+            else {
+                // Handle the case where "?" could start (or not start) a run
+                recurse(springs[1..].to_vec(), counts.clone(), 0)
+                    + recurse(springs[1..].to_vec(), counts.clone(), 1)
+            }
+        }
+
+        _ => unreachable!("springs are checked by find_combinations")
+    }
+}
+
+/// Counts the arrangements for a line of the condition record, unfolding it to
+/// five times its size first if `unfold` is set.
+fn find_combinations(line: &str, unfold: bool) -> PuzzleResult<u64> {
+    let (springs, counts) = line
+        .split_once(' ')
+        .ok_or(PuzzleError::parse("line should have a space between the springs and the counts"))?;
+
+    if let Some(c) = springs.chars().find(|c| !matches!(c, '.' | '#' | '?')) {
+        return Err(PuzzleError::parse(format!("unknown spring condition `{}`", c)));
+    }
+
+    let counts = counts
+        .split(',')
+        .map(|s| s.parse::<u64>().context(format!("count `{}` is not a number", s)))
+        .collect::<Result<Vec<u64>,_>>()?;
+
+    let (springs, counts) = if unfold {
+        ([springs].repeat(5).join("?"), counts.repeat(5))
+    }
+    else {
+        (springs.to_owned(), counts)
+    };
+
+    // shouldn't really do this, but given the input, we should be safe, and
+    // it should be faster than applying ranges on strings
+    let springs = springs.as_bytes();
+
+    Ok(recurse(springs.to_vec(), counts.to_vec(), 0))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = Vec<String>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        Ok(input
+            .lines()
+            .map(str::to_owned)
+            .collect())
+    }
+
+    fn part1(lines: &Self::Input) -> PuzzleResult<Self::Part1> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| find_combinations(line, false).at_line(index + 1, line))
+            .sum()
+    }
+
+    fn part2(lines: &Self::Input) -> PuzzleResult<Self::Part2> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| find_combinations(line, true).at_line(index + 1, line))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_1() {
+        let data = "???.### 1,1,3";
+
+        let answer = find_combinations(data, false).expect("This should work");
+
+        assert_eq!(answer, 1, "Only one possibility");
+    }
+
+    #[test]
+    fn test_case_2() {
+        let data = ".??..??...?##. 1,1,3";
+
+        let answer = find_combinations(data, false).expect("This should work");
+
+        assert_eq!(answer, 4, "4 possibilities");
+    }
+
+    #[test]
+    fn test_case_3() {
+        let data = "?###???????? 3,2,1";
+
+        let answer = find_combinations(data, false).expect("This should work");
+
+        assert_eq!(answer, 10, "Should be 10");
+    }
+}
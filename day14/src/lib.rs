@@ -0,0 +1,172 @@
+use std::fmt::{Display,Formatter};
+use std::str::FromStr;
+use aoc_utils::cycle::find_cycle;
+use aoc_utils::grid::Grid;
+use aoc_utils::prelude::*;
+
+/// The platform of rocks, rounded (`O`) and cube-shaped (`#`)
+#[derive(Clone,Eq,Hash,PartialEq)]
+pub struct Platform {
+    grid: Grid<char>
+}
+
+impl FromStr for Platform {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_with(s, |c| match c {
+            'O' | '#' | '.' => Ok(c),
+            _ => Err(PuzzleError::parse(format!("unknown rock `{}`", c)))
+        })?;
+
+        Ok(Self { grid })
+    }
+}
+
+impl Platform {
+    pub fn shift_north(&mut self) -> u64 {
+        let mut weight: u64 = 0;
+        for x in 0..self.grid.width() {
+            let mut last_immovable = 0;
+            for y in 0..self.grid.height() {
+                match self.grid[(x, y)] {
+                    '#' => last_immovable = y + 1,
+                    'O' => {
+                        weight += (self.grid.height() - last_immovable) as u64;
+                        self.grid[(x, y)] = '.';
+                        self.grid[(x, last_immovable)] = 'O';
+                        last_immovable += 1;
+                    }
+                    _ => ()
+                }
+            }
+        }
+        weight
+    }
+
+    pub fn shift_south(&mut self) -> u64 {
+        let mut weight: u64 = 0;
+        for x in 0..self.grid.width() {
+            let mut last_immovable = self.grid.height() - 1;
+            for y in 0..self.grid.height() {
+                let dy = self.grid.height() - y - 1;
+                match self.grid[(x, dy)] {
+                    // saturate, as this only reaches zero on the last row
+                    '#' => last_immovable = dy.saturating_sub(1),
+                    'O' => {
+                        weight += last_immovable as u64;
+                        self.grid[(x, dy)] = '.';
+                        self.grid[(x, last_immovable)] = 'O';
+                        last_immovable = last_immovable.saturating_sub(1);
+                    }
+                    _ => ()
+                }
+            }
+        }
+
+        weight
+    }
+    
+    pub fn shift_west(&mut self) -> u64 {
+        let mut weight: u64 = 0;
+        for y in 0..self.grid.height() {
+            let mut last_immovable = 0;
+            for x in 0..self.grid.width() {
+                match self.grid[(x, y)] {
+                    '#' => last_immovable = x + 1,
+                    'O' => {
+                        weight += (self.grid.width() - last_immovable) as u64;
+                        self.grid[(x, y)] = '.';
+                        self.grid[(last_immovable, y)] = 'O';
+                        last_immovable += 1;
+                    }
+                    _ => ()
+                }
+            }
+        }
+
+        weight
+    }
+
+    pub fn shift_east(&mut self) -> u64 {
+        let mut weight: u64 = 0;
+        for y in 0..self.grid.height() {
+            let mut last_immovable = self.grid.width() - 1;
+            for x in 0..self.grid.width() {
+                let dx = self.grid.width() - x - 1;
+                match self.grid[(dx, y)] {
+                    // saturate, as this only reaches zero on the last column
+                    '#' => last_immovable = dx.saturating_sub(1),
+                    'O' => {
+                        weight += last_immovable as u64;
+                        self.grid[(dx, y)] = '.';
+                        self.grid[(last_immovable, y)] = 'O';
+                        last_immovable = last_immovable.saturating_sub(1);
+                    }
+                    _ => ()
+                }
+            }
+        }
+
+        weight
+    }
+
+    pub fn north_weight(&self) -> u64 {
+        let mut weight: u64 = 0;
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                if self.grid[(x, y)] == 'O' {
+                    weight += (self.grid.height() - y) as u64;
+                }
+            }
+        }
+        weight
+    }
+
+    /// Runs one spin cycle, tilting north, west, south and then east
+    fn spin_cycle(&mut self) {
+        self.shift_north();
+        self.shift_west();
+        self.shift_south();
+        self.shift_east();
+    }
+
+    /// The platform after `iterations` spin cycles
+    pub fn spin(&self, iterations: usize) -> Platform {
+        let cycle = find_cycle(self.clone(), |platform| {
+            let mut platform = platform.clone();
+            platform.spin_cycle();
+            platform
+        });
+
+        cycle.get(iterations).clone()
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }    
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+
+    type Input = Platform;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(platform: &Self::Input) -> PuzzleResult<Self::Part1> {
+        Ok(platform.clone().shift_north())
+    }
+
+    fn part2(platform: &Self::Input) -> PuzzleResult<Self::Part2> {
+        Ok(platform.spin(1_000_000_000).north_weight())
+    }
+}
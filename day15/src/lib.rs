@@ -0,0 +1,112 @@
+use aoc_utils::prelude::*;
+
+struct HashMap<'a> {
+    pub buckets: Vec<Vec<(&'a [u8], u8)>>
+}
+
+impl<'a> HashMap<'a> {
+    fn new() -> Self {
+        let mut v = Vec::new();
+        for _ in 0..256 {
+            v.push(Vec::new());
+        }
+        Self {
+            buckets: v
+        }
+    }
+
+    fn add(&mut self, key: &'a [u8], value: u8) {
+        let h: usize = key.hash().into();
+
+        for entry in self.buckets[h].iter_mut() {
+            if entry.0 == key {
+                entry.1 = value;
+                return;
+            }
+        }
+
+        self.buckets[h].push((key, value))
+    }
+
+    fn del(&mut self, key: &'a [u8]) {
+        let h: usize = key.hash().into();
+
+        if let Some(index) = self.buckets[h].iter().position(|(k, _)| *k == key) {
+            self.buckets[h].remove(index);
+        }
+    }
+
+    fn focal_power(&self) -> u64 {
+        self.buckets
+            .iter()
+            .enumerate()
+            .flat_map(|(bucket_index, bucket)| bucket
+                .iter()
+                .enumerate()
+                .map(move |(lens_index,(_name, power))| ((bucket_index + 1) * (lens_index + 1) * *power as usize) as u64)
+            )
+            .sum()
+    }
+}
+
+trait AdventHash<T> {
+    fn hash(&self) -> T;
+}
+
+impl<> AdventHash<u8> for &[u8] {
+    fn hash(&self) -> u8 {
+        self
+            .iter()
+            .fold(0u8, |hash, &ch| if ch == b'\n' {
+                    hash
+                }
+                else {
+                    hash
+                        .wrapping_add(ch)
+                        .wrapping_mul(17)
+                }
+            )
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+
+    type Input = Vec<u8>;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        Ok(input.as_bytes().to_vec())
+    }
+
+    fn part1(buf: &Self::Input) -> PuzzleResult<Self::Part1> {
+        Ok(buf
+            .split(|b| *b == b',')
+            .map(|seq| seq.hash() as u32)
+            .sum())
+    }
+
+    fn part2(buf: &Self::Input) -> PuzzleResult<Self::Part2> {
+        let mut hashmap = HashMap::new();
+
+        for segment in buf.split(|b| *b == b',') {
+            let invalid = || PuzzleError::parse(format!("invalid step `{}`", String::from_utf8_lossy(segment).trim()));
+
+            let operation = segment
+                .iter()
+                .position(|p| matches!(p, b'-' | b'='))
+                .ok_or_else(invalid)?;
+
+            match (segment[operation], segment.get(operation + 1)) {
+                (b'=', Some(digit @ b'0'..=b'9')) => hashmap.add(&segment[0..operation], digit - b'0'),
+                (b'-', _) => hashmap.del(&segment[0..operation]),
+                _ => return Err(invalid())
+            }
+        }
+
+        Ok(hashmap.focal_power())
+    }
+}
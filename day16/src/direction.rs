@@ -1,8 +0,0 @@
-use crate::point::Point;
-
-pub enum Direction {
-    Up(Point),
-    Down(Point),
-    Left(Point),
-    Right(Point)
-}
@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use aoc_utils::geom::{Direction,Point};
+use aoc_utils::grid::Grid;
+use aoc_utils::prelude::*;
+
+
+#[derive(Clone)]
+pub struct LaserBoard {
+    grid: Grid<char>
+}
+
+impl std::fmt::Debug for LaserBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Board is {}x{}", self.grid.width(), self.grid.height())?;
+        write!(f, "{}", self.grid)
+    }
+}
+
+impl FromStr for LaserBoard {
+    type Err = PuzzleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_with(s, |c| match c {
+            '.' | '|' | '-' | '/' | '\\' => Ok(c),
+            _ => Err(PuzzleError::parse(format!("unknown tile `{}`", c)))
+        })?;
+
+        Ok(Self { grid })
+    }
+}
+
+impl LaserBoard {
+    /// Where a beam leaving `origin` heading in `direction` goes next, if it
+    /// stays on the board
+    fn beam(&self, origin: Point<usize>, direction: Direction) -> Option<(Point<usize>, Direction)> {
+        origin
+            .step_within(direction, self.grid.width(), self.grid.height())
+            .map(|p| (p, direction))
+    }
+
+    /// Returns the number of "energised tiles"
+    fn layzer_boim(&mut self, start: Point<usize>, heading: Direction) -> usize {
+        let mut visited_special_tiles: HashSet<Point<usize>> = HashSet::new();
+
+        let mut continuations: Vec<(Point<usize>, Direction)> = vec![(start, heading)];
+
+        while let Some((mut origin, heading)) = continuations.pop() {
+            loop {
+                match self.grid[origin] {
+                    '|' => {
+                        if !visited_special_tiles.insert(origin) {
+                            break;
+                        }
+
+                        if heading.is_horizontal() {
+                            continuations.extend(self.beam(origin, Direction::North));
+                            continuations.extend(self.beam(origin, Direction::South));
+                            break;
+                        }
+                    },
+
+                    '-' => {
+                        if !visited_special_tiles.insert(origin) {
+                            break;
+                        }
+
+                        if heading.is_vertical() {
+                            continuations.extend(self.beam(origin, Direction::West));
+                            continuations.extend(self.beam(origin, Direction::East));
+                            break;
+                        }
+                    }
+
+                    '/' => {
+                        visited_special_tiles.insert(origin);
+
+                        let turned = if heading.is_vertical() {
+                            heading.turn_right()
+                        }
+                        else {
+                            heading.turn_left()
+                        };
+
+                        continuations.extend(self.beam(origin, turned));
+                        break;
+                    }
+
+                    '\\' => {
+                        visited_special_tiles.insert(origin);
+
+                        let turned = if heading.is_vertical() {
+                            heading.turn_left()
+                        }
+                        else {
+                            heading.turn_right()
+                        };
+
+                        continuations.extend(self.beam(origin, turned));
+                        break;
+                    }
+                    
+                    '.' => self.grid[origin] = '#',
+
+                    '#' => (),
+
+                    b => unreachable!("unknown tile {b:?} at {} should have been caught by from_str", origin)
+                }
+
+                if let Some((new_origin, _)) = self.beam(origin, heading) {
+                    origin = new_origin;
+                }
+                else {
+                    break;
+                }
+            }
+        }
+
+        self.grid.iter().filter(|(_, c)| **c == '#').count() + visited_special_tiles.len()
+
+    }
+}
+
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+
+    type Input = LaserBoard;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(board: &Self::Input) -> PuzzleResult<Self::Part1> {
+        Ok(board.clone().layzer_boim(Point::new(0, 0), Direction::East))
+    }
+
+    fn part2(board: &Self::Input) -> PuzzleResult<Self::Part2> {
+        let width = board.grid.width();
+        let height = board.grid.height();
+        
+        let up_maximum = (0..width)
+            .map(|x|
+                board.clone().layzer_boim(Point::new(x, height - 1), Direction::North)
+            )
+            .max()
+            .unwrap_or(0);
+
+        let down_maximum = (0..width)
+            .map(|x|
+                board.clone().layzer_boim(Point::new(x, 0), Direction::South)
+            )
+            .max()
+            .unwrap_or(0);
+
+        let left_maximum = (0..height)
+            .map(|y|
+                board.clone().layzer_boim(Point::new(width - 1, y), Direction::West)
+            )
+            .max()
+            .unwrap_or(0);
+
+        let right_maximum = (0..height)
+            .map(|y|
+                board.clone().layzer_boim(Point::new(0, y), Direction::East)
+            )
+            .max()
+            .unwrap_or(0);
+
+        let answer = up_maximum
+            .max(down_maximum)
+            .max(left_maximum)
+            .max(right_maximum);
+
+        Ok(answer)
+    }
+}
@@ -0,0 +1,38 @@
+use aoc_utils::prelude::*;
+
+pub mod worldmap;
+
+use worldmap::WorldMap;
+
+fn best_route(worldmap: &WorldMap, ultra: bool) -> PuzzleResult<u32> {
+    let end = worldmap.width()
+        .checked_sub(1)
+        .zip(worldmap.height().checked_sub(1))
+        .ok_or(PuzzleErrorKind::MissingInput)?;
+
+    worldmap
+        .find_best_route((0usize, 0usize).into(), end.into(), ultra)
+        .ok_or(PuzzleError::new(PuzzleErrorKind::InputError, "no route to the factory"))
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+
+    type Input = WorldMap;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(worldmap: &Self::Input) -> PuzzleResult<Self::Part1> {
+        best_route(worldmap, false)
+    }
+
+    fn part2(worldmap: &Self::Input) -> PuzzleResult<Self::Part2> {
+        best_route(worldmap, true)
+    }
+}
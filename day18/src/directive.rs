@@ -1,70 +1,65 @@
-#[derive(Debug)]
-pub struct DirectiveError {
-    msg: &'static str
-}
-
-impl DirectiveError {
-    pub fn new(msg: &'static str) -> Self {
-        Self {
-            msg
-        }
-    }
-}
-
-impl std::fmt::Display for DirectiveError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "Directive error: {}", self.msg)
-    }
-}
-
-impl std::error::Error for DirectiveError {}
+use aoc_utils::geom::{Direction,Point};
+use aoc_utils::prelude::*;
 
 pub struct Directive {
-    pub dx: isize,
-    pub dy: isize,
+    pub direction: Direction,
+    pub amount: isize
 }
 
-impl TryFrom<String> for Directive {
-    type Error = DirectiveError;
-    fn try_from(value: String) -> Result<Self, Self::Error> {
+impl Directive {
+    /// Parses a line of the dig plan, taking the direction and amount from
+    /// the colour code instead if `from_color` is set.
+    pub fn parse(value: &str, from_color: bool) -> PuzzleResult<Self> {
         let mut fields = value.split_ascii_whitespace();
 
-        let direction = fields.next().ok_or_else(|| DirectiveError::new("No direction"))?;
-        let amount: isize = isize::from_str_radix(
-                fields.next().ok_or_else(|| DirectiveError::new("No amount"))?,
-                10
-            )
-            .map_err(|_| DirectiveError::new("Amount is unparseable"))?;
-        let color = fields.next().ok_or_else(|| DirectiveError::new("No color"))?;
+        let direction = fields.next().ok_or(PuzzleError::parse("no direction"))?;
+        let amount = fields.next().ok_or(PuzzleError::parse("no amount"))?;
+        let color = fields.next().ok_or(PuzzleError::parse("no color"))?;
 
-        if fields.next().is_some_and(|x| x.trim().len() > 0) {
-            return Err(DirectiveError::new("Too much input"));
+        if fields.next().is_some() {
+            return Err(PuzzleError::parse("too much input"));
         }
 
-        let (dx, dy) = if cfg!(feature = "part2") {
-            let amount = isize::from_str_radix(&color[2..=6], 16).map_err(|_| DirectiveError::new("Unparsable color"))?;
+        let (direction, amount) = if from_color {
+            let hex = color
+                .strip_prefix("(#")
+                .and_then(|c| c.strip_suffix(')'))
+                .filter(|c| c.len() == 6 && c.is_ascii())
+                .ok_or(PuzzleError::parse(format!("color `{}` should look like `(#70c710)`", color)))?;
+
+            let amount = isize::from_str_radix(&hex[..5], 16)
+                .context(format!("unparsable color `{}`", color))?;
 
-            match &color[7..=7] {
-                "0" => (amount, 0),
-                "1" => (0, amount),
-                "2" => (-amount, 0),
-                "3" => (0, -amount),
-                _ => return Err(DirectiveError::new("Unknown direction"))
+            match &hex[5..] {
+                "0" => (Direction::East, amount),
+                "1" => (Direction::South, amount),
+                "2" => (Direction::West, amount),
+                "3" => (Direction::North, amount),
+                d => return Err(PuzzleError::parse(format!("unknown direction `{}` in color", d)))
             }
         }
         else {
+            let amount: isize = amount
+                .parse()
+                .context(format!("amount `{}` is unparsable", amount))?;
+
             match direction {
-                "L" => (-amount, 0),
-                "R" => (amount, 0),
-                "U" => (0, -amount),
-                "D" => (0, amount),
-                _ => return Err(DirectiveError::new("Unknown direction"))
+                "L" => (Direction::West, amount),
+                "R" => (Direction::East, amount),
+                "U" => (Direction::North, amount),
+                "D" => (Direction::South, amount),
+                _ => return Err(PuzzleError::parse(format!("unknown direction `{}`", direction)))
             }
         };
 
         Ok(Self {
-            dx,
-            dy
+            direction,
+            amount
         })
     }
+
+    /// How far this directive moves the digger
+    pub fn offset(&self) -> Point<isize> {
+        self.direction.delta() * self.amount
+    }
 }
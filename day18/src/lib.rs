@@ -0,0 +1,59 @@
+use aoc_utils::geom::Point;
+use aoc_utils::prelude::*;
+
+pub mod directive;
+use directive::Directive;
+
+fn lagoon_size(directives: &[Directive]) -> isize {
+    let corners = directives
+        .iter()
+        .fold(vec![Point::default()], |mut corners, d| {
+            let last = corners[corners.len() - 1];
+            corners.push(last + d.offset());
+            corners
+        });
+
+    // shoelace formula for the area inside the trench, plus the half of the
+    // trench itself that lies outside that area
+    let (area, perimeter) = corners
+        .windows(2)
+        .fold((0isize, 0isize), |(area, perimeter), points| (
+            area + points[0].x * points[1].y - points[1].x * points[0].y,
+            perimeter + points[0].manhattan_distance(&points[1])
+        ));
+
+    area.abs() / 2 + perimeter / 2 + 1
+}
+
+fn dig_plan(lines: &[String], from_color: bool) -> PuzzleResult<Vec<Directive>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| Directive::parse(line, from_color).at_line(index + 1, line))
+        .collect()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+
+    type Input = Vec<String>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        Ok(input
+            .lines()
+            .map(str::to_owned)
+            .collect())
+    }
+
+    fn part1(lines: &Self::Input) -> PuzzleResult<Self::Part1> {
+        Ok(lagoon_size(&dig_plan(lines, false)?))
+    }
+
+    fn part2(lines: &Self::Input) -> PuzzleResult<Self::Part2> {
+        Ok(lagoon_size(&dig_plan(lines, true)?))
+    }
+}
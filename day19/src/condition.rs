@@ -1,4 +1,4 @@
-use std::ops::Range;
+use aoc_utils::interval::IntervalSet;
 
 #[derive(Clone,Copy,Debug)]
 pub enum ConditionResult<'a> {
@@ -8,10 +8,13 @@ pub enum ConditionResult<'a> {
     Next(&'a str)
 }
 
+/// The possible values of each of a part's ratings (x, m, a and s)
+pub type RatingRanges = [IntervalSet<u32>; 4];
+
 #[derive(Clone,Debug)]
 pub enum ConditionResult2<'a> {
-    FallThrough(&'a str, [Range<u32>; 4]),
-    Approved([Range<u32>; 4]),
-    Rejected([Range<u32>; 4]),
-    Next(&'a str, [Range<u32>; 4], [Range<u32>; 4])
+    FallThrough(&'a str, RatingRanges),
+    Approved(RatingRanges),
+    Rejected(RatingRanges),
+    Next(&'a str, RatingRanges, RatingRanges)
 }
@@ -0,0 +1,85 @@
+use aoc_utils::prelude::*;
+
+pub mod condition;
+pub mod part;
+pub mod workflow;
+pub mod workflow2;
+
+use part::Part;
+use workflow::WorkflowDB;
+use workflow2::WorkflowDB as WorkflowDB2;
+
+/// The workflows (still as text, as the workflow databases borrow from it)
+/// and the parts to be sorted
+pub struct System {
+    workflows: String,
+    parts: Vec<Part>
+}
+
+/// Splits a workflow line into its name and its comma separated expressions
+fn split_workflow(line: &str) -> PuzzleResult<(&str, &str)> {
+    let (name, expressions) = line
+        .trim_end()
+        .strip_suffix('}')
+        .and_then(|l| l.split_once('{'))
+        .ok_or(PuzzleError::parse("workflow should look like `name{rule,...}`"))?;
+
+    Ok((name, expressions))
+}
+
+/// Parses each workflow line and adds it to a database with `add`
+fn load_workflows<'a, DB, F>(workflows: &'a str, mut db: DB, add: F) -> PuzzleResult<DB>
+    where F: Fn(&mut DB, &'a str, &'a str) -> PuzzleResult<()>
+{
+    for (index, line) in workflows.lines().enumerate() {
+        let (name, expressions) = split_workflow(line).at_line(index + 1, line)?;
+        add(&mut db, name, expressions).at_token(index + 1, line, expressions)?;
+    }
+
+    Ok(db)
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+
+    type Input = System;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        let (workflows, parts) = input
+            .split_once("\n\n")
+            .ok_or(PuzzleError::parse("missing blank line between the workflows and the parts"))?;
+
+        // parts start after the workflows and the blank line
+        let first_part_line = workflows.lines().count() + 2;
+
+        let parts = parts
+            .lines()
+            .enumerate()
+            .map(|(index, line)| Part::try_from(line).at_line(first_part_line + index, line))
+            .collect::<PuzzleResult<Vec<_>>>()?;
+
+        Ok(System {
+            workflows: workflows.to_owned(),
+            parts
+        })
+    }
+
+    fn part1(system: &Self::Input) -> PuzzleResult<Self::Part1> {
+        let workflowdb = load_workflows(&system.workflows, WorkflowDB::new(), WorkflowDB::add_workflow)?;
+
+        system.parts
+            .iter()
+            .map(|p| workflowdb.part_value(p))
+            .sum()
+    }
+
+    fn part2(system: &Self::Input) -> PuzzleResult<Self::Part2> {
+        let workflowdb = load_workflows(&system.workflows, WorkflowDB2::new(), WorkflowDB2::add_workflow)?;
+
+        workflowdb.total_parts()
+    }
+}
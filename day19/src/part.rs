@@ -1,4 +1,4 @@
-pub struct PartError{}
+use aoc_utils::prelude::*;
 
 pub struct Part {
     pub x: u32,
@@ -7,16 +7,25 @@ pub struct Part {
     pub s: u32,
 }
 
-fn parse_component(s: &str) -> Result<(&str, u32),PartError> {
-    let equals = s.chars().position(|c| c == '=').ok_or_else(|| PartError {})?;
-    Ok((&s[0..equals], u32::from_str_radix(&s[equals+1..], 10).map_err(|_| PartError{})?))
+fn parse_component(s: &str) -> PuzzleResult<(&str, u32)> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or(PuzzleError::parse(format!("rating `{}` should look like `x=123`", s)))?;
+
+    let value = value
+        .parse::<u32>()
+        .context(format!("rating `{}` is not a number", s))?;
+
+    Ok((name, value))
 }
 
 impl TryFrom<&str> for Part {
-    type Error = PartError;
+    type Error = PuzzleError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let value = value.strip_prefix('{').ok_or_else(|| PartError {})?;
-        let value = value.strip_suffix('}').ok_or_else(|| PartError {})?;
+        let value = value
+            .strip_prefix('{')
+            .and_then(|v| v.strip_suffix('}'))
+            .ok_or(PuzzleError::parse("part should be surrounded by `{}`"))?;
 
         let components: Vec<(&str,u32)> = value
             .split(',')
@@ -24,7 +33,7 @@ impl TryFrom<&str> for Part {
             .collect::<Result<Vec<_>,_>>()?;
 
         if components.len() != 4 {
-            return Err(PartError {})
+            return Err(PuzzleError::parse(format!("expected 4 ratings, found {}", components.len())))
         }
 
         Ok(Self {
@@ -40,4 +49,4 @@ impl Part {
     pub fn value(&self) -> u32 {
         self.x + self.m + self.a + self.s
     }
-}
\ No newline at end of file
+}
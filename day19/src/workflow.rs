@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use aoc_utils::error::PuzzleErrorKind;
+use aoc_utils::error::{PuzzleError,PuzzleErrorKind};
 use aoc_utils::result::PuzzleResult;
 
 use crate::condition::ConditionResult;
@@ -10,18 +10,24 @@ type Condition<'a, 'b> = dyn Fn(&'b Part) -> ConditionResult<'a> + 'a;
 
 pub fn parse_condition<'a, 'b>(expression: &'a str) -> PuzzleResult<Box<Condition<'a, 'b>>> {
     let op = expression
-        .find(|ch| matches!(ch, '<' | '>'));
+        .find(['<', '>']);
 
     let Some(op) = op else {
         return Ok(Box::new(|_| ConditionResult::Next(expression)));    
     };
 
     let colon = op + expression[op..]
-        .find(|ch| ch == ':')
-        .ok_or_else(|| PuzzleErrorKind::ParseError)?;
-
-    let field = &expression[0..op];
-    let value = u32::from_str_radix(&expression[op+1..colon], 10)?;
+        .find(':')
+        .ok_or(PuzzleError::parse(format!("rule `{}` has no `:`", expression)))?;
+
+    let rating: fn(&Part) -> u32 = match &expression[0..op] {
+        "x" => |part| part.x,
+        "m" => |part| part.m,
+        "a" => |part| part.a,
+        "s" => |part| part.s,
+        field => return Err(PuzzleError::parse(format!("unknown rating `{}` in rule `{}`", field, expression)))
+    };
+    let value = expression[op+1..colon].parse::<u32>()?;
     let result = ConditionResult::Next(&expression[colon+1..]);
 
     let op = &expression[op..=op];
@@ -32,15 +38,7 @@ pub fn parse_condition<'a, 'b>(expression: &'a str) -> PuzzleResult<Box<Conditio
     };
 
     Ok(Box::new(move |part: &'b Part| {
-        let part_field = match field {
-            "x" => part.x,
-            "m" => part.m,
-            "a" => part.a,
-            "s" => part.s,
-            _ => panic!("Weird rule ...")
-        };
-
-        if func(&part_field, &value) {
+        if func(&rating(part), &value) {
             result
         }
         else {
@@ -53,6 +51,12 @@ pub struct WorkflowDB<'a, 'b> {
     functions: HashMap<&'a str, Vec<Box<Condition<'a, 'b>>>>,
 }
 
+impl<'a, 'b> Default for WorkflowDB<'a, 'b> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a, 'b> WorkflowDB<'a, 'b> {
     pub fn new() -> Self {
         let mut wdb = Self {
@@ -76,17 +80,23 @@ impl<'a, 'b> WorkflowDB<'a, 'b> {
         Ok(())
     }
 
-    pub fn part_value(&self, part: &'b Part) -> u32 {
-        let mut conditions = self.functions.get("in").expect("Missing workflow named 'in'");
+    fn workflow(&self, name: &str) -> PuzzleResult<&Vec<Box<Condition<'a, 'b>>>> {
+        self.functions
+            .get(name)
+            .ok_or(PuzzleError::new(PuzzleErrorKind::InputError, format!("missing workflow `{}`", name)))
+    }
+
+    pub fn part_value(&self, part: &'b Part) -> PuzzleResult<u32> {
+        let mut conditions = self.workflow("in")?;
 
         loop {
             for condition in conditions {
                 match condition(part) {
                     ConditionResult::Nothing => (),
-                    ConditionResult::Approved => return part.value(),
-                    ConditionResult::Rejected => return 0,
+                    ConditionResult::Approved => return Ok(part.value()),
+                    ConditionResult::Rejected => return Ok(0),
                     ConditionResult::Next(next) => {
-                        conditions = self.functions.get(next).expect("Missing workflow");
+                        conditions = self.workflow(next)?;
                         break;
                     }
                 }
@@ -1,47 +1,51 @@
 use std::collections::HashMap;
-use std::ops::Range;
-use aoc_utils::rangeutils::Intersect;
+use aoc_utils::interval::IntervalSet;
 
-use aoc_utils::error::{PuzzleErrorKind, PuzzleError};
+use aoc_utils::error::{PuzzleError,PuzzleErrorKind};
 use aoc_utils::result::PuzzleResult;
 
-use crate::condition::ConditionResult2;
+use crate::condition::{ConditionResult2,RatingRanges};
 
-type Condition2<'a> = dyn Fn([Range<u32>; 4]) -> ConditionResult2<'a> + 'a;
+type Condition2<'a> = dyn Fn(RatingRanges) -> ConditionResult2<'a> + 'a;
 
 fn parse_condition_part2<'a>(expression: &'a str) -> PuzzleResult<Box<Condition2<'a>>> {
     let op = expression
-        .find(|ch| matches!(ch, '<' | '>'));
+        .find(['<', '>']);
 
     let Some(op) = op else {
         return Ok(Box::new(|ranges| ConditionResult2::FallThrough(expression, ranges)));
     };
 
     let colon = op + expression[op..]
-        .find(|ch| ch == ':')
-        .ok_or_else(|| PuzzleErrorKind::ParseError)?;
+        .find(':')
+        .ok_or(PuzzleError::parse(format!("rule `{}` has no `:`", expression)))?;
 
     let range_index = match &expression[0..op] {
         "x" => 0,
         "m" => 1,
         "a" => 2,
         "s" => 3,
-        _ => panic!("Weird rule...")
+        field => return Err(PuzzleError::parse(format!("unknown rating `{}` in rule `{}`", field, expression)))
     };
-    let value = u32::from_str_radix(&expression[op+1..colon], 10)?;
+    let value = expression[op+1..colon].parse::<u32>()?;
     let next = &expression[colon+1..];
 
-    let op = &expression[op..=op];
-    let (this_range, opposite_range) = match op {
-        "<" => (1..value, value..4001),
-        ">" => (value+1..4001, 1..value+1),
+    // the values that pass are either side of where the ranges are split
+    let (split, passes_below) = match &expression[op..=op] {
+        "<" => (value, true),
+        ">" => (value + 1, false),
         _ => return Err(PuzzleErrorKind::InputError.into())
     };
 
     Ok(Box::new(move |mut ranges| {
         let mut remainder = ranges.clone();
-        ranges[range_index] = ranges[range_index].intersect(&this_range).unwrap_or(0..0);
-        remainder[range_index] = remainder[range_index].intersect(&opposite_range).unwrap_or(0..0);
+        let (below, above) = ranges[range_index].split_at(split);
+        (ranges[range_index], remainder[range_index]) = if passes_below {
+            (below, above)
+        }
+        else {
+            (above, below)
+        };
         ConditionResult2::Next(next, ranges, remainder)
     }))
 }
@@ -50,14 +54,20 @@ pub struct WorkflowDB<'a> {
     functions: HashMap<&'a str, Vec<Box<Condition2<'a>>>>,
 }
 
+impl<'a> Default for WorkflowDB<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> WorkflowDB<'a> {
     pub fn new() -> Self {
         let mut wdb = Self {
             functions: HashMap::new()
         };
 
-        wdb.functions.insert("A", vec![Box::new(|ranges| ConditionResult2::Approved(ranges))]);
-        wdb.functions.insert("R", vec![Box::new(|ranges| ConditionResult2::Rejected(ranges))]);
+        wdb.functions.insert("A", vec![Box::new(ConditionResult2::Approved)]);
+        wdb.functions.insert("R", vec![Box::new(ConditionResult2::Rejected)]);
 
         wdb
     }
@@ -73,25 +83,25 @@ impl<'a> WorkflowDB<'a> {
         Ok(())
     }
 
-    pub fn total_parts(&self) -> u64 {
+    pub fn total_parts(&self) -> PuzzleResult<u64> {
         let mut approved = 0;
-        let mut rejected = 0;
 
         let mut continuations = Vec::new();
 
-        continuations.push(("in", [1..4001, 1..4001, 1..4001, 1..4001]));
+        let all_ratings = IntervalSet::from(1..4001);
+        continuations.push(("in", [all_ratings.clone(), all_ratings.clone(), all_ratings.clone(), all_ratings]));
 
         while let Some((workflow_name, mut main_ranges)) = continuations.pop() {
-            let conditions = self.functions.get(workflow_name).expect("Missing workflow");
+            let conditions = self.functions
+                .get(workflow_name)
+                .ok_or(PuzzleError::new(PuzzleErrorKind::InputError, format!("missing workflow `{}`", workflow_name)))?;
 
             for condition in conditions {
                 match condition(main_ranges.clone()) {
                     ConditionResult2::Approved(ranges) => {
-                        approved += ranges.iter().map(|r| r.len() as u64).product::<u64>();
-                    },
-                    ConditionResult2::Rejected(ranges) => {
-                        rejected += ranges.iter().map(|r| r.len() as u64).product::<u64>();
+                        approved += ranges.iter().map(|r| r.total_length() as u64).product::<u64>();
                     },
+                    ConditionResult2::Rejected(_) => {},
                     ConditionResult2::Next(next, ranges, remainder) => {
                         continuations.push((next, ranges));
                         main_ranges = remainder;
@@ -103,8 +113,6 @@ impl<'a> WorkflowDB<'a> {
             }
         }
 
-
-        dbg!(256_000_000_000_000 - rejected);
-        approved
+        Ok(approved)
     }
 }
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use aoc_utils::prelude::*;
+
+/// A number of cubes of each colour, e.g. a handful shown in a round or the
+/// contents of a bag
+#[derive(Clone,Debug,Default,Eq,PartialEq)]
+pub struct Cubes {
+    counts: BTreeMap<String, u32>
+}
+
+impl Cubes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many cubes there are of `colour`, which is zero for a colour that
+    /// was never mentioned
+    pub fn get(&self, colour: &str) -> u32 {
+        self.counts.get(colour).copied().unwrap_or_default()
+    }
+
+    /// Adds `count` cubes of `colour`. Fails if that makes too many to count.
+    pub fn add(&mut self, colour: &str, count: u32) -> PuzzleResult<()> {
+        let current = self.counts.entry(colour.to_owned()).or_default();
+        *current = current
+            .checked_add(count)
+            .ok_or_else(|| PuzzleError::new(PuzzleErrorKind::InputError, format!("too many {} cubes to count", colour)))?;
+
+        Ok(())
+    }
+
+    /// Each colour and how many cubes there are of it, in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.counts.iter().map(|(colour, count)| (colour.as_str(), *count))
+    }
+
+    /// Raises each colour's count to at least the count in `other`
+    pub fn include(&mut self, other: &Cubes) {
+        for (colour, count) in other.iter() {
+            let current = self.counts.entry(colour.to_owned()).or_default();
+            *current = (*current).max(count);
+        }
+    }
+
+    /// The colours there are more of than `limits` allows
+    pub fn over_limits<'a>(&'a self, limits: &'a Cubes) -> impl Iterator<Item = &'a str> {
+        self.iter()
+            .filter(|(colour, count)| *count > limits.get(colour))
+            .map(|(colour, _)| colour)
+    }
+}
+
+/// Reads counts like `3 blue, 4 red`. A colour given more than once is added
+/// up.
+impl FromStr for Cubes {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cubes = Cubes::new();
+
+        for count in s.split(',') {
+            let (number, colour) = count
+                .trim()
+                .split_once(' ')
+                .ok_or(PuzzleError::parse(format!("expected `NUMBER COLOUR`, found `{}`", count.trim())))?;
+
+            let number = number
+                .parse::<u32>()
+                .context(format!("`{}` is not a number of cubes", number))?;
+
+            let colour = colour.trim();
+            if colour.is_empty() || colour.contains(char::is_whitespace) {
+                return Err(PuzzleError::parse(format!("`{}` is not a colour", colour)));
+            }
+
+            cubes.add(colour, number)?;
+        }
+
+        Ok(cubes)
+    }
+}
+
+impl fmt::Display for Cubes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (colour, count)) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, colour)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let cubes: Cubes = "3 blue, 4 red, 2 blue".parse().expect("Should parse");
+        assert_eq!(cubes.get("blue"), 5);
+        assert_eq!(cubes.get("red"), 4);
+        assert_eq!(cubes.get("green"), 0);
+        assert_eq!(cubes.to_string(), "5 blue, 4 red");
+
+        assert!("blue 3".parse::<Cubes>().is_err());
+        assert!("3".parse::<Cubes>().is_err());
+
+        let error = "4294967295 red, 1 red".parse::<Cubes>().expect_err("Should overflow");
+        assert!(error.message().is_some_and(|m| m.contains("too many red cubes")), "{}", error);
+    }
+
+    #[test]
+    fn test_over_limits() {
+        let limits: Cubes = "12 red, 13 green, 14 blue".parse().expect("Should parse");
+        let cubes: Cubes = "20 red, 13 green, 1 purple".parse().expect("Should parse");
+        assert_eq!(cubes.over_limits(&limits).collect::<Vec<_>>(), vec!["purple", "red"]);
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use aoc_utils::prelude::*;
+
+pub mod cubes;
+
+use cubes::Cubes;
+
+/// The bag part 1 asks about, unless `--limits` gives another
+pub const DEFAULT_LIMITS: &str = "12 red, 13 green, 14 blue";
+
+/// Reads the contents of the bag for part 1, e.g. `12 red, 13 green`
+pub fn parse_limits(s: &str) -> PuzzleResult<Cubes> {
+    s.parse::<Cubes>()
+        .map_err(|e| PuzzleError::new(PuzzleErrorKind::InvalidArgument, format!("`{}` is not a list of cube limits", s)).with_source(e))
+}
+
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Cubes>
+}
+
+impl Game {
+    /// The fewest cubes of each colour that the bag could have held
+    pub fn minimal_bag(&self) -> Cubes {
+        self.rounds
+            .iter()
+            .fold(Cubes::new(), |mut bag, round| {
+                bag.include(round);
+                bag
+            })
+    }
+
+    /// The colours that make the game impossible with a bag holding `limits`,
+    /// as some round showed more of them than that
+    pub fn impossible_colours<'a>(&'a self, limits: &'a Cubes) -> BTreeSet<&'a str> {
+        self.rounds
+            .iter()
+            .flat_map(|round| round.over_limits(limits))
+            .collect()
+    }
+
+    /// The number of cubes of each of `colours` in the minimal bag multiplied
+    /// together. A colour this game never shows means a bag with none of it.
+    pub fn power(&self, colours: &Cubes) -> PuzzleResult<u64> {
+        let bag = self.minimal_bag();
+
+        colours
+            .iter()
+            .try_fold(1u64, |power, (colour, _)| power.checked_mul(u64::from(bag.get(colour))))
+            .ok_or_else(|| PuzzleError::new(PuzzleErrorKind::InputError, format!("the power of game {} is too big", self.id)))
+    }
+
+    pub fn is_possible(&self, limits: &Cubes) -> bool {
+        self.impossible_colours(limits).is_empty()
+    }
+}
+
+impl FromStr for Game {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> PuzzleResult<Self> {
+        let (id, rounds) = s
+            .strip_prefix("Game ")
+            .and_then(|s| s.split_once(':'))
+            .ok_or(PuzzleError::parse("expected `Game ID: ROUND; ROUND; ...`"))?;
+
+        let id = id
+            .parse::<u32>()
+            .context(format!("`{}` is not a game number", id))?;
+
+        Ok(Self {
+            id,
+            rounds: rounds
+                .split(';')
+                .map(Cubes::from_str)
+                .collect::<PuzzleResult<Vec<Cubes>>>()?
+        })
+    }
+}
+
+/// The smallest limits under which every game is possible, i.e. the most
+/// cubes of each colour shown in any round
+pub fn limits_for_all(games: &[Game]) -> Cubes {
+    games
+        .iter()
+        .fold(Cubes::new(), |mut limits, game| {
+            limits.include(&game.minimal_bag());
+            limits
+        })
+}
+
+/// The sum of the ids of the games that are possible with `limits`
+pub fn possible_games_total(games: &[Game], limits: &Cubes) -> PuzzleResult<u32> {
+    games
+        .iter()
+        .filter(|g| g.is_possible(limits))
+        .try_fold(0u32, |total, g| total.checked_add(g.id))
+        .ok_or_else(|| PuzzleError::new(PuzzleErrorKind::InputError, "the total of the game ids is too big"))
+}
+
+/// The games, and the bag part 1 checks them against
+pub struct Record {
+    pub games: Vec<Game>,
+    pub limits: Cubes
+}
+
+/// Reads one game per line
+fn parse_games(input: &str) -> PuzzleResult<Vec<Game>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| Game::from_str(line).at_line(index + 1, line))
+        .collect()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+
+    type Input = Record;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    /// `--limits "12 red, 13 green, 14 blue"` sets the bag for part 1
+    const OPTIONS: &'static [PuzzleOption] = &[PuzzleOption::Value("limits")];
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        Ok(Record { games: parse_games(input)?, limits: parse_limits(DEFAULT_LIMITS)? })
+    }
+
+    fn parse_with_args(input: &str, args: &PuzzleArgs) -> PuzzleResult<Self::Input> {
+        let limits = parse_limits(args.value("limits").unwrap_or(DEFAULT_LIMITS))?;
+
+        Ok(Record { games: parse_games(input)?, limits })
+    }
+
+    fn part1(record: &Self::Input) -> PuzzleResult<Self::Part1> {
+        possible_games_total(&record.games, &record.limits)
+    }
+
+    fn part2(record: &Self::Input) -> PuzzleResult<Self::Part2> {
+        let games = &record.games;
+        let colours = limits_for_all(games);
+
+        games
+            .iter()
+            .try_fold(0u64, |total, g| total
+                .checked_add(g.power(&colours)?)
+                .ok_or_else(|| PuzzleError::new(PuzzleErrorKind::InputError, "the total power is too big")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME: &str = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+
+    #[test]
+    fn test_minimal_bag() {
+        let game: Game = GAME.parse().expect("Should parse");
+        assert_eq!(game.id, 3);
+        assert_eq!(game.minimal_bag().to_string(), "6 blue, 13 green, 20 red");
+    }
+
+    #[test]
+    fn test_impossible_colours() {
+        let game: Game = GAME.parse().expect("Should parse");
+
+        let limits = parse_limits(DEFAULT_LIMITS).expect("Should parse");
+        assert_eq!(game.impossible_colours(&limits).into_iter().collect::<Vec<_>>(), vec!["red"]);
+        assert!(!game.is_possible(&limits));
+
+        let limits = parse_limits("20 red, 13 green, 5 blue").expect("Should parse");
+        assert_eq!(game.impossible_colours(&limits).into_iter().collect::<Vec<_>>(), vec!["blue"]);
+
+        assert!(game.is_possible(&game.minimal_bag()));
+    }
+
+    #[test]
+    fn test_power() {
+        let game: Game = GAME.parse().expect("Should parse");
+        let colours = parse_limits("1 red, 1 green, 1 blue, 1 purple").expect("Should parse");
+        assert_eq!(game.power(&game.minimal_bag()).expect("Should fit"), 6 * 13 * 20);
+        assert_eq!(game.power(&colours).expect("Should fit"), 0);
+
+        let huge: Game = "Game 1: 4294967295 red, 4294967295 green, 4294967295 blue".parse().expect("Should parse");
+        assert!(huge.power(&huge.minimal_bag()).is_err());
+        assert!(Day2::part2(&Day2::parse("Game 1: 4294967295 red, 4294967295 green, 4294967295 blue").expect("Should parse")).is_err());
+    }
+
+    #[test]
+    fn test_limits_option() {
+        let input = format!("{}\nGame 4: 1 red, 2 green\n", GAME);
+        assert_eq!(Day2::part1(&Day2::parse(&input).expect("Should parse")).expect("Should solve"), 4);
+
+        let args = PuzzleArgs::parse([String::from("--limits=20 red, 13 green, 6 blue")], Day2::OPTIONS).expect("Should parse");
+        let record = Day2::parse_with_args(&input, &args).expect("Should parse");
+        assert_eq!(record.limits.get("red"), 20);
+        assert_eq!(Day2::part1(&record).expect("Should solve"), 3 + 4);
+
+        let args = PuzzleArgs::parse([String::from("--limits"), String::from("twelve red")], Day2::OPTIONS).expect("Should parse");
+        let error = Day2::parse_with_args(&input, &args).err().expect("Should reject the limits");
+        assert!(matches!(error.kind(), PuzzleErrorKind::InvalidArgument));
+    }
+}
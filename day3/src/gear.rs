@@ -0,0 +1,61 @@
+/// How to combine the numbers around a gear into one value
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Max
+}
+
+impl Aggregation {
+    pub fn apply<I: IntoIterator<Item = u32>>(&self, values: I) -> u64 {
+        let values = values.into_iter().map(u64::from);
+
+        match self {
+            Aggregation::Product => values.product(),
+            Aggregation::Sum => values.sum(),
+            Aggregation::Max => values.max().unwrap_or_default()
+        }
+    }
+}
+
+/// Which symbols count as gears, and what their gear value is
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct GearRule {
+    /// The symbols that can be gears, where an empty list means any symbol
+    pub symbols: Vec<char>,
+    /// How many numbers a gear must touch, exactly
+    pub neighbours: usize,
+    pub aggregation: Aggregation
+}
+
+impl GearRule {
+    pub fn new(symbols: &str, neighbours: usize, aggregation: Aggregation) -> Self {
+        Self {
+            symbols: symbols.chars().collect(),
+            neighbours,
+            aggregation
+        }
+    }
+
+    /// A `*` touching exactly two numbers, which are multiplied together
+    pub fn standard() -> Self {
+        Self::new("*", 2, Aggregation::Product)
+    }
+
+    pub fn matches_symbol(&self, symbol: char) -> bool {
+        self.symbols.is_empty() || self.symbols.contains(&symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregation() {
+        assert_eq!(Aggregation::Product.apply([3, 4, 5]), 60);
+        assert_eq!(Aggregation::Sum.apply([3, 4, 5]), 12);
+        assert_eq!(Aggregation::Max.apply([3, 4, 5]), 5);
+        assert_eq!(Aggregation::Max.apply([]), 0);
+    }
+}
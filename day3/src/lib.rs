@@ -0,0 +1,32 @@
+use aoc_utils::prelude::*;
+
+pub mod gear;
+pub mod schematic;
+
+use gear::GearRule;
+use schematic::Schematic;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+
+    type Input = Schematic;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(schematic: &Self::Input) -> PuzzleResult<Self::Part1> {
+        Ok(schematic
+            .part_numbers()
+            .map(|number| number.value)
+            .sum())
+    }
+
+    fn part2(schematic: &Self::Input) -> PuzzleResult<Self::Part2> {
+        Ok(schematic.gear_total(&GearRule::standard()))
+    }
+}
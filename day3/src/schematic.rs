@@ -0,0 +1,180 @@
+use std::ops::Range;
+use std::str::FromStr;
+use aoc_utils::geom::Point;
+use aoc_utils::prelude::*;
+use crate::gear::GearRule;
+
+/// A number in the schematic, which covers `columns` of its row
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct Number {
+    pub row: usize,
+    pub columns: Range<usize>,
+    pub value: u32
+}
+
+/// Any character that isn't a digit or a `.`
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct Symbol {
+    pub position: Point<usize>,
+    pub symbol: char
+}
+
+/// An engine schematic, with its numbers and symbols indexed by row so that
+/// finding the neighbours of one only looks at the rows either side of it
+#[derive(Clone,Debug,Default)]
+pub struct Schematic {
+    /// In order of row, then column
+    numbers: Vec<Number>,
+    /// In order of row, then column
+    symbols: Vec<Symbol>,
+    /// Where each row starts in `numbers`, with an extra entry for the end
+    number_rows: Vec<usize>,
+    /// Where each row starts in `symbols`, with an extra entry for the end
+    symbol_rows: Vec<usize>
+}
+
+impl Schematic {
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// The rows either side of `row` and the row itself, as far as they exist
+    fn rows_around(&self, row: usize) -> Range<usize> {
+        row.saturating_sub(1)..(row + 2).min(self.number_rows.len() - 1)
+    }
+
+    /// The numbers touching `symbol`, including diagonally
+    pub fn numbers_next_to<'a>(&'a self, symbol: &Symbol) -> impl Iterator<Item = &'a Number> {
+        let column = symbol.position.x;
+
+        self.rows_around(symbol.position.y)
+            .flat_map(move |row| {
+                let numbers = &self.numbers[self.number_rows[row]..self.number_rows[row + 1]];
+                let first = numbers.partition_point(|n| n.columns.end < column);
+                numbers[first..]
+                    .iter()
+                    .take_while(move |n| n.columns.start <= column + 1)
+            })
+    }
+
+    /// The symbols touching `number`, including diagonally
+    pub fn symbols_next_to<'a>(&'a self, number: &Number) -> impl Iterator<Item = &'a Symbol> {
+        let columns = number.columns.start.saturating_sub(1)..number.columns.end + 1;
+
+        self.rows_around(number.row)
+            .flat_map(move |row| {
+                let symbols = &self.symbols[self.symbol_rows[row]..self.symbol_rows[row + 1]];
+                let first = symbols.partition_point(|s| s.position.x < columns.start);
+                symbols[first..]
+                    .iter()
+                    .take_while(move |s| s.position.x < columns.end)
+            })
+    }
+
+    /// The numbers touching at least one symbol
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .filter(|n| self.symbols_next_to(n).next().is_some())
+    }
+
+    /// The symbols that are gears under `rule`, along with the numbers they
+    /// touch
+    pub fn gears<'a>(&'a self, rule: &'a GearRule) -> impl Iterator<Item = (&'a Symbol, Vec<&'a Number>)> {
+        self.symbols
+            .iter()
+            .filter(|s| rule.matches_symbol(s.symbol))
+            .map(|s| (s, self.numbers_next_to(s).collect::<Vec<_>>()))
+            .filter(|(_, numbers)| numbers.len() == rule.neighbours)
+    }
+
+    /// The values of every gear under `rule` added up
+    pub fn gear_total(&self, rule: &GearRule) -> u64 {
+        self.gears(rule)
+            .map(|(_, numbers)| rule.aggregation.apply(numbers.iter().map(|n| n.value)))
+            .sum()
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut schematic = Schematic::default();
+
+        for (row, line) in s.lines().enumerate() {
+            schematic.number_rows.push(schematic.numbers.len());
+            schematic.symbol_rows.push(schematic.symbols.len());
+
+            // the number currently being read, as its starting column and byte
+            let mut number_start: Option<(usize, usize)> = None;
+            let mut chars = line.trim_end().char_indices().enumerate().peekable();
+
+            while let Some((column, (offset, c))) = chars.next() {
+                if c.is_ascii_digit() {
+                    let (start_column, start_offset) = *number_start.get_or_insert((column, offset));
+
+                    if !chars.peek().is_some_and(|(_, (_, next))| next.is_ascii_digit()) {
+                        let digits = &line[start_offset..offset + 1];
+                        let value = digits
+                            .parse::<u32>()
+                            .context("number is too big")
+                            .at(Location::span(row + 1, line, start_offset..offset + 1))?;
+
+                        schematic.numbers.push(Number { row, columns: start_column..column + 1, value });
+                        number_start = None;
+                    }
+                }
+                else if c != '.' {
+                    schematic.symbols.push(Symbol { position: Point::new(column, row), symbol: c });
+                }
+            }
+        }
+
+        schematic.number_rows.push(schematic.numbers.len());
+        schematic.symbol_rows.push(schematic.symbols.len());
+
+        Ok(schematic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gear::Aggregation;
+
+    const SCHEMATIC: &str = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+
+    #[test]
+    fn test_neighbours() {
+        let schematic: Schematic = SCHEMATIC.parse().expect("Should parse");
+        let star = &schematic.symbols()[0];
+        assert_eq!(star.position, Point::new(3, 1));
+        assert_eq!(schematic.numbers_next_to(star).map(|n| n.value).collect::<Vec<_>>(), vec![467, 35]);
+
+        let n114 = &schematic.numbers()[1];
+        assert_eq!(schematic.symbols_next_to(n114).count(), 0);
+        assert_eq!(schematic.part_numbers().map(|n| n.value).sum::<u32>(), 4361);
+    }
+
+    #[test]
+    fn test_gear_rules() {
+        let schematic: Schematic = SCHEMATIC.parse().expect("Should parse");
+        assert_eq!(schematic.gear_total(&GearRule::standard()), 467835);
+
+        // `*` at (3, 4) only touches 617
+        assert_eq!(schematic.gear_total(&GearRule::new("*", 1, Aggregation::Sum)), 617);
+        assert_eq!(schematic.gear_total(&GearRule::new("#$", 1, Aggregation::Max)), 633 + 664);
+        assert_eq!(schematic.gears(&GearRule::new("", 2, Aggregation::Sum)).count(), 2);
+    }
+
+    #[test]
+    fn test_any_symbol() {
+        let schematic: Schematic = "1~.\n..2\n3..".parse().expect("Should parse");
+        assert_eq!(schematic.part_numbers().map(|n| n.value).collect::<Vec<_>>(), vec![1, 2]);
+    }
+}
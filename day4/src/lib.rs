@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use aoc_utils::prelude::*;
+
+pub mod scratchcards;
+
+use scratchcards::Scratchcards;
+
+pub struct Card {
+    pub id: usize,
+    pub winning_numbers: HashSet<u32>,
+    pub game_numbers: HashSet<u32>
+}
+
+impl Card {
+    pub fn get_winning_number_count(&self) -> usize {
+        self.winning_numbers
+            .intersection(&self.game_numbers)
+            .count()
+    }
+
+    pub fn get_points(&self) -> u32 {
+        match self.get_winning_number_count() {
+            0 => 0,
+            v => 1 << (v - 1)
+        }
+    }
+}
+
+fn parse_numbers(s: &str) -> PuzzleResult<HashSet<u32>> {
+    s.split_ascii_whitespace()
+        .map(|n| n.parse::<u32>().context(format!("`{}` is not a number", n)))
+        .collect()
+}
+
+impl FromStr for Card {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> PuzzleResult<Self> {
+        let (id, numbers) = s
+            .strip_prefix("Card ")
+            .and_then(|s| s.split_once(':'))
+            .ok_or(PuzzleError::parse("expected `Card ID: WINNING NUMBERS | NUMBERS`"))?;
+
+        let (winning_numbers, game_numbers) = numbers
+            .split_once('|')
+            .ok_or(PuzzleError::parse("expected a `|` between the winning numbers and the card's numbers"))?;
+
+        let id = id
+            .trim()
+            .parse::<usize>()
+            .context(format!("`{}` is not a card number", id.trim()))?;
+
+        Ok(Self {
+            id,
+            winning_numbers: parse_numbers(winning_numbers)?,
+            game_numbers: parse_numbers(game_numbers)?
+        })
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+
+    /// `--audit csv|json` writes how each card did instead of solving the
+    /// puzzle
+    const OPTIONS: &'static [PuzzleOption] = &[PuzzleOption::Value("audit")];
+
+    type Input = Scratchcards;
+    type Part1 = u32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(cards: &Self::Input) -> PuzzleResult<Self::Part1> {
+        Ok(cards.points())
+    }
+
+    fn part2(cards: &Self::Input) -> PuzzleResult<Self::Part2> {
+        cards.total_cards()
+    }
+}
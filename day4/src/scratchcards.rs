@@ -0,0 +1,316 @@
+use std::io::Write;
+use std::str::FromStr;
+use serde::Serialize;
+use aoc_utils::prelude::*;
+use crate::Card;
+
+/// A pile of scratchcards, numbered one after another
+pub struct Scratchcards {
+    cards: Vec<Card>
+}
+
+/// Copies of one card won by another
+#[derive(Clone,Debug,Eq,PartialEq,Serialize)]
+pub struct Received {
+    pub card: usize,
+    pub copies: usize
+}
+
+/// How one card did once all the copies have been won
+#[derive(Clone,Debug,Eq,PartialEq,Serialize)]
+pub struct CardAudit {
+    pub id: usize,
+    pub matches: usize,
+    pub points: u32,
+    /// How many of this card there ended up being, including the original
+    pub copies: usize,
+    /// How many copies of later cards all of those won
+    pub copies_won: usize,
+    /// The cards that won copies of this one
+    pub received_from: Vec<Received>
+}
+
+/// Checks that a card numbered `id` can come after the card numbered
+/// `previous` in a pile starting at card `first`, which means it must be the
+/// next number along
+fn check_order(first: usize, previous: usize, id: usize) -> PuzzleResult<()> {
+    if id == previous + 1 {
+        Ok(())
+    }
+    else if (first..=previous).contains(&id) {
+        Err(PuzzleError::new(PuzzleErrorKind::InputError, format!("card {} appears more than once", id)))
+    }
+    else if id > previous {
+        Err(PuzzleError::new(PuzzleErrorKind::InputError, format!("card {} is missing before card {}", previous + 1, id)))
+    }
+    else {
+        Err(PuzzleError::new(PuzzleErrorKind::InputError, format!("card {} comes after card {}", id, previous)))
+    }
+}
+
+/// The position of the first card that isn't numbered one after the cards
+/// before it, and what's wrong with it
+fn find_misordered(cards: &[Card]) -> Option<(usize, PuzzleError)> {
+    cards
+        .windows(2)
+        .enumerate()
+        .find_map(|(index, pair)| check_order(cards[0].id, pair[0].id, pair[1].id)
+            .err()
+            .map(|e| (index + 1, e)))
+}
+
+/// The formats the audit can be written in
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum AuditFormat {
+    Csv,
+    Json
+}
+
+impl FromStr for AuditFormat {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(AuditFormat::Csv),
+            "json" => Ok(AuditFormat::Json),
+            _ => Err(PuzzleError::new(PuzzleErrorKind::InvalidArgument, format!("`{}` is not an audit format, expected `csv` or `json`", s)))
+        }
+    }
+}
+
+impl Scratchcards {
+    /// Fails unless the cards are numbered one after another
+    pub fn new(cards: Vec<Card>) -> PuzzleResult<Self> {
+        match find_misordered(&cards) {
+            Some((_, error)) => Err(error),
+            None => Ok(Self { cards })
+        }
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// The points of every card added up
+    pub fn points(&self) -> u32 {
+        self.cards
+            .iter()
+            .map(Card::get_points)
+            .sum()
+    }
+
+    /// Plays out the cards, returning how many of each there end up being and
+    /// which cards each one's copies came from. Fails if a card would win
+    /// copies of cards past the end of the pile, or if there would be too
+    /// many copies to count.
+    fn play(&self) -> PuzzleResult<(Vec<usize>, Vec<Vec<Received>>)> {
+        let mut copies: Vec<usize> = vec![1; self.cards.len()];
+        let mut received: Vec<Vec<Received>> = vec![Vec::new(); self.cards.len()];
+
+        for (index, card) in self.cards.iter().enumerate() {
+            let matches = card.get_winning_number_count();
+            if index + matches >= self.cards.len() {
+                return Err(PuzzleError::new(PuzzleErrorKind::InputError,
+                    format!("card {} would win copies of cards past the end", card.id)));
+            }
+
+            for won in index + 1..=index + matches {
+                copies[won] = copies[won]
+                    .checked_add(copies[index])
+                    .ok_or_else(|| PuzzleError::new(PuzzleErrorKind::InputError,
+                        format!("card {} would have too many copies to count", self.cards[won].id)))?;
+                received[won].push(Received { card: card.id, copies: copies[index] });
+            }
+        }
+
+        Ok((copies, received))
+    }
+
+    /// How many cards there end up being once all the copies have been won
+    pub fn total_cards(&self) -> PuzzleResult<usize> {
+        self.play()?.0
+            .iter()
+            .try_fold(0usize, |total, copies| total.checked_add(*copies))
+            .ok_or_else(|| PuzzleError::new(PuzzleErrorKind::InputError, "there would be too many cards to count"))
+    }
+
+    /// How each card did
+    pub fn audit(&self) -> PuzzleResult<Vec<CardAudit>> {
+        let (copies, received) = self.play()?;
+
+        self.cards
+            .iter()
+            .zip(copies)
+            .zip(received)
+            .map(|((card, copies), received_from)| {
+                let matches = card.get_winning_number_count();
+                let copies_won = copies
+                    .checked_mul(matches)
+                    .ok_or_else(|| PuzzleError::new(PuzzleErrorKind::InputError,
+                        format!("card {} would win too many copies to count", card.id)))?;
+
+                Ok(CardAudit {
+                    id: card.id,
+                    matches,
+                    points: card.get_points(),
+                    copies,
+                    copies_won,
+                    received_from
+                })
+            })
+            .collect()
+    }
+
+    /// Writes the audit as CSV, with the cards that won copies of each card
+    /// as `card:copies` pairs separated by `;`
+    pub fn write_audit_csv<W: Write>(&self, mut writer: W) -> PuzzleResult<()> {
+        let audit = self.audit()?;
+        writeln!(writer, "id,matches,points,copies,copies_won,received_from")?;
+
+        for audit in audit {
+            let received_from: Vec<String> = audit.received_from
+                .iter()
+                .map(|r| format!("{}:{}", r.card, r.copies))
+                .collect();
+
+            writeln!(writer, "{},{},{},{},{},{}",
+                audit.id, audit.matches, audit.points, audit.copies, audit.copies_won, received_from.join(";"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the audit as a JSON array with an object for each card
+    pub fn write_audit_json<W: Write>(&self, writer: W) -> PuzzleResult<()> {
+        serde_json::to_writer_pretty(writer, &self.audit()?)
+            .map_err(std::io::Error::from)?;
+
+        Ok(())
+    }
+
+    pub fn write_audit<W: Write>(&self, format: AuditFormat, writer: W) -> PuzzleResult<()> {
+        match format {
+            AuditFormat::Csv => self.write_audit_csv(writer),
+            AuditFormat::Json => self.write_audit_json(writer)
+        }
+    }
+}
+
+/// Reads one card per line, failing at the line of the first card that's out
+/// of order
+impl FromStr for Scratchcards {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().collect();
+        let cards = lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| Card::from_str(line).at_line(index + 1, line))
+            .collect::<PuzzleResult<Vec<Card>>>()?;
+
+        match find_misordered(&cards) {
+            Some((index, error)) => Err(error).at_line(index + 1, lines[index]),
+            None => Ok(Self { cards })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARDS: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_audit() {
+        let cards: Scratchcards = CARDS.parse().expect("Should parse");
+        assert_eq!(cards.points(), 13);
+        assert_eq!(cards.total_cards().expect("Should play"), 30);
+
+        let audit = cards.audit().expect("Should play");
+        assert_eq!(audit[3], CardAudit {
+            id: 4,
+            matches: 1,
+            points: 1,
+            copies: 8,
+            copies_won: 8,
+            received_from: vec![
+                Received { card: 1, copies: 1 },
+                Received { card: 2, copies: 2 },
+                Received { card: 3, copies: 4 }
+            ]
+        });
+
+        let mut csv = Vec::new();
+        cards.write_audit_csv(&mut csv).expect("Should write");
+        let csv = String::from_utf8(csv).expect("Should be UTF-8");
+        assert_eq!(csv.lines().nth(4), Some("4,1,1,8,8,1:1;2:2;3:4"));
+
+        let mut json = Vec::new();
+        cards.write_audit_json(&mut json).expect("Should write");
+        let json: serde_json::Value = serde_json::from_slice(&json).expect("Should be JSON");
+        assert_eq!(json[3]["received_from"][2]["copies"], 4);
+    }
+
+    #[test]
+    fn test_card_order() {
+        let card = |id| format!("Card {}: 1 | 2", id);
+        let pile = |ids: &[usize]| ids.iter().map(|id| card(*id)).collect::<Vec<_>>().join("\n").parse::<Scratchcards>();
+
+        assert!(pile(&[3, 4, 5]).is_ok());
+        assert!(pile(&[1, 2, 2]).is_err());
+        assert!(pile(&[1, 3]).is_err());
+        assert!(pile(&[2, 1]).is_err());
+
+        let error = pile(&[1, 2, 4, 5]).err().expect("Should find the gap");
+        assert_eq!(error.location().map(|l| l.line), Some(3));
+        assert!(error.message().is_some_and(|m| m.contains("card 3 is missing")), "{}", error);
+
+        let cards = ["Card 1: 1 | 2", "Card 1: 1 | 2"].map(|c| c.parse::<Card>().expect("Should parse"));
+        assert!(Scratchcards::new(cards.into()).is_err());
+    }
+
+    #[test]
+    fn test_too_many_copies() {
+        // each card wins a copy of every card after it, so there are 2^(n-1)
+        // copies of the nth card
+        let pile = (1..=70)
+            .map(|id| {
+                let numbers = (1..=70 - id).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+                format!("Card {}: {} | {}", id, numbers, numbers)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .parse::<Scratchcards>()
+            .expect("Should parse");
+
+        let error = pile.total_cards().expect_err("Should overflow");
+        assert!(error.message().is_some_and(|m| m.contains("too many copies")), "{}", error);
+    }
+
+    #[test]
+    fn test_failed_audit_writes_nothing() {
+        let cards: Scratchcards = "Card 1: 1 2 | 1 2\nCard 2: 3 | 4".parse().expect("Should parse");
+
+        let mut csv = Vec::new();
+        assert!(cards.write_audit(AuditFormat::Csv, &mut csv).is_err());
+        assert!(csv.is_empty());
+
+        let mut json = Vec::new();
+        assert!(cards.write_audit(AuditFormat::Json, &mut json).is_err());
+        assert!(json.is_empty());
+    }
+
+    #[test]
+    fn test_audit_format() {
+        assert_eq!("csv".parse::<AuditFormat>().expect("Should parse"), AuditFormat::Csv);
+        assert_eq!("json".parse::<AuditFormat>().expect("Should parse"), AuditFormat::Json);
+        assert!("xml".parse::<AuditFormat>().is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use aoc_utils::interval::IntervalSet;
+use aoc_utils::prelude::*;
+use aoc_utils::search::bfs;
+use crate::mapping::Mapping;
+
+/// Every mapping table in the almanac, keyed by the categories it maps from and
+/// to. Together they link the categories up into a graph.
+#[derive(Clone,Debug,Default)]
+pub struct Almanac {
+    tables: HashMap<String, HashMap<String, Mapping>>
+}
+
+impl Almanac {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `source-to-destination` table. Fails if there's already a
+    /// table between them or if the tables would go round in a cycle.
+    pub fn add(&mut self, source: &str, destination: &str, mapping: Mapping) -> PuzzleResult<()> {
+        if self.table(source, destination).is_some() {
+            return Err(PuzzleError::new(PuzzleErrorKind::InputError, format!("there is already a `{}-to-{}` map", source, destination)));
+        }
+
+        // the new table closes a cycle if `destination` already leads to `source`
+        let cycle = bfs(destination, |category| self.destinations(category), |category| *category == source);
+        if let Some(cycle) = cycle {
+            return Err(PuzzleError::new(PuzzleErrorKind::InputError,
+                format!("`{}-to-{}` map makes a cycle: {} -> {}", source, destination, source, cycle.path.join(" -> "))));
+        }
+
+        self.tables
+            .entry(source.to_owned())
+            .or_default()
+            .insert(destination.to_owned(), mapping);
+
+        Ok(())
+    }
+
+    fn table(&self, source: &str, destination: &str) -> Option<&Mapping> {
+        self.tables
+            .get(source)
+            .and_then(|tables| tables.get(destination))
+    }
+
+    /// The categories that `category` has a table to
+    fn destinations(&self, category: &str) -> Vec<&str> {
+        self.tables
+            .get(category)
+            .map(|tables| tables.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// The categories that have a table to `category`
+    fn sources(&self, category: &str) -> Vec<&str> {
+        self.tables
+            .iter()
+            .filter(|(_, tables)| tables.contains_key(category))
+            .map(|(source, _)| source.as_str())
+            .collect()
+    }
+
+    fn contains(&self, category: &str) -> bool {
+        self.tables.contains_key(category) || !self.sources(category).is_empty()
+    }
+
+    /// The shortest chain of categories leading from `from` to `to`, following
+    /// tables in either direction
+    pub fn route<'a>(&'a self, from: &'a str, to: &str) -> PuzzleResult<Vec<&'a str>> {
+        for category in [from, to] {
+            if !self.contains(category) {
+                return Err(PuzzleError::new(PuzzleErrorKind::InputError, format!("no maps to or from `{}`", category)));
+            }
+        }
+
+        bfs(from, |category| {
+                let mut next = self.destinations(category);
+                next.extend(self.sources(category));
+                next
+            },
+            |category| *category == to)
+            .map(|result| result.path)
+            .ok_or(PuzzleError::new(PuzzleErrorKind::InputError, format!("no chain of maps links `{}` to `{}`", from, to)))
+    }
+
+    /// A single mapping taking numbers in the `from` category to the `to`
+    /// category. Tables are followed backwards where needed, as long as they
+    /// can be undone.
+    pub fn mapping(&self, from: &str, to: &str) -> PuzzleResult<Mapping> {
+        self.route(from, to)?
+            .windows(2)
+            .try_fold(Mapping::new(), |combined, step| {
+                let (source, destination) = (step[0], step[1]);
+
+                let mapping = match self.table(source, destination) {
+                    Some(mapping) => mapping.clone(),
+                    None => self.table(destination, source)
+                        .and_then(Mapping::inverse)
+                        .ok_or(PuzzleError::new(PuzzleErrorKind::InputError,
+                            format!("`{}-to-{}` map can't be reversed as it maps different numbers to the same place", destination, source)))?
+                };
+
+                Ok(combined.then(&mapping))
+            })
+    }
+
+    /// Every number in the `from` category that ends up in `values` in the
+    /// `to` category. Each table is traced back to its sources, so unlike
+    /// [`Almanac::mapping`] this works even if a table can't be undone.
+    pub fn trace_back(&self, from: &str, to: &str, values: &IntervalSet<i64>) -> PuzzleResult<IntervalSet<i64>> {
+        let route = self.route(from, to)?;
+
+        Ok(route
+            .windows(2)
+            .rev()
+            .fold(values.clone(), |values, step| match self.table(step[0], step[1]) {
+                Some(mapping) => mapping.preimage(&values),
+                // this step followed a table backwards, so trace it forwards
+                None => self.table(step[1], step[0])
+                    .map(|mapping| mapping.apply_set(&values))
+                    .unwrap_or(values)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift(offset: i64) -> Mapping {
+        [(0..10, offset), (offset..offset + 10, -offset)].into_iter().collect()
+    }
+
+    fn almanac(tables: &[(&str, &str, Mapping)]) -> PuzzleResult<Almanac> {
+        let mut almanac = Almanac::new();
+        for (source, destination, mapping) in tables {
+            almanac.add(source, destination, mapping.clone())?;
+        }
+        Ok(almanac)
+    }
+
+    #[test]
+    fn test_route_in_either_direction() {
+        let almanac = almanac(&[("a", "b", shift(10)), ("b", "c", shift(20)), ("c", "d", shift(30))])
+            .expect("Should build the almanac");
+
+        assert_eq!(almanac.route("b", "d").expect("Should find a route"), vec!["b", "c", "d"]);
+        assert_eq!(almanac.route("d", "a").expect("Should find a route"), vec!["d", "c", "b", "a"]);
+
+        let forwards = almanac.mapping("a", "d").expect("Should map forwards");
+        let backwards = almanac.mapping("d", "a").expect("Should map backwards");
+        for value in -5..50 {
+            assert_eq!(backwards.apply(forwards.apply(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_trace_back() {
+        // b -> c squashes 0..10 onto 10..20, so it can't be undone
+        let squash: Mapping = [(0..10, 10)].into_iter().collect();
+        let almanac = almanac(&[("a", "b", shift(100)), ("b", "c", squash)]).expect("Should build the almanac");
+        assert!(almanac.mapping("c", "a").is_err());
+
+        let sources = almanac.trace_back("a", "c", &IntervalSet::from(15..16)).expect("Should trace back");
+        let expected: IntervalSet<i64> = [15..16, 105..106].into_iter().collect();
+        assert_eq!(sources, expected);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let error = almanac(&[("a", "b", shift(10)), ("b", "c", shift(20)), ("c", "a", shift(30))])
+            .expect_err("Should reject the cycle");
+        assert!(error.message().is_some_and(|m| m.contains("c -> a -> b -> c")), "{}", error);
+    }
+
+    #[test]
+    fn test_missing_link() {
+        let almanac = almanac(&[("a", "b", shift(10)), ("c", "d", shift(20))]).expect("Should build the almanac");
+        assert!(almanac.route("a", "d").is_err());
+        assert!(almanac.route("a", "z").is_err());
+    }
+}
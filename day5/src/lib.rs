@@ -0,0 +1,233 @@
+use std::ops::Range;
+use aoc_utils::interval::IntervalSet;
+use aoc_utils::prelude::*;
+
+pub mod almanac;
+pub mod mapping;
+
+use almanac::Almanac;
+use mapping::Mapping;
+
+enum State {
+    Seeds,
+    TableName,
+    TableRow
+}
+
+/// Each of the seed numbers on its own, as in part 1
+pub fn seed_numbers(seeds: &[i64]) -> IntervalSet<i64> {
+    seeds
+        .iter()
+        .map(|c| *c..*c+1)
+        .collect()
+}
+
+/// The seed numbers read as pairs of start and length, as in part 2
+pub fn seed_ranges(seeds: &[i64]) -> PuzzleResult<IntervalSet<i64>> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err(PuzzleError::new(PuzzleErrorKind::InputError, "seed ranges should come in pairs of start and length"));
+    }
+
+    Ok(seeds
+        .chunks(2)
+        .map(|v| v[0]..v[0]+v[1])
+        .collect())
+}
+
+/// The `seeds` that end up somewhere in `locations`
+pub fn seeds_for_locations(almanac: &Almanac, seeds: &IntervalSet<i64>, locations: Range<i64>) -> PuzzleResult<IntervalSet<i64>> {
+    Ok(almanac
+        .trace_back("seed", "location", &IntervalSet::from(locations))?
+        .intersection(seeds))
+}
+
+/// Reads a range of locations written as `START..END`, e.g. `0..50`
+pub fn parse_locations(s: &str) -> PuzzleResult<Range<i64>> {
+    let invalid = || PuzzleError::new(PuzzleErrorKind::InvalidArgument, format!("expected a range of locations like `0..50`, found `{}`", s));
+
+    let (start, end) = s.split_once("..").ok_or_else(invalid)?;
+    let start = start.trim().parse::<i64>().map_err(|_| invalid())?;
+    let end = end.trim().parse::<i64>().map_err(|_| invalid())?;
+
+    if start >= end {
+        return Err(invalid());
+    }
+
+    Ok(start..end)
+}
+
+fn lowest_location(almanac: &Almanac, seeds: IntervalSet<i64>) -> PuzzleResult<i64> {
+    almanac
+        .mapping("seed", "location")?
+        .apply_set(&seeds)
+        .min()
+        .ok_or(PuzzleError::new(PuzzleErrorKind::InputError, "no seeds"))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+
+    /// `--locations START..END` lists the seeds that end up in those
+    /// locations instead of solving the puzzle
+    const OPTIONS: &'static [PuzzleOption] = &[PuzzleOption::Value("locations")];
+
+    type Input = (Vec<i64>, Almanac);
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        let mut almanac: Almanac = Almanac::new();
+        // the line the current table's name is on, and the categories it maps between
+        let mut current_table: Option<(usize, &str, &str, &str)> = None;
+        let mut current_mapping = Mapping::new();
+        let mut seeds: Vec<i64> = Vec::new();
+        
+        let mut parse_state: State = State::Seeds;
+
+        // Chain an empty string to ensure the last table is parsed
+        for (index, raw_line) in input.lines().chain([""]).enumerate() {
+            let line_number = index + 1;
+            let line = raw_line.trim();
+            if line.is_empty() {
+                if let Some((name_line_number, name_line, source, destination)) = current_table {
+                    almanac
+                        .add(source, destination, current_mapping)
+                        .at_line(name_line_number, name_line)?;
+                    current_table = None;
+                    current_mapping = Mapping::new();
+                    parse_state = State::TableName;
+                }
+
+                continue;
+            }
+
+            match parse_state {
+                State::Seeds => {
+                    let values = line
+                        .strip_prefix("seeds: ")
+                        .ok_or(PuzzleError::parse("expected a `seeds:` line"))
+                        .at_line(line_number, raw_line)?;
+
+                    seeds = values
+                        .split_ascii_whitespace()
+                        .map(|s| s.parse::<i64>()
+                            .context("seed numbers must be integers")
+                            .at_token(line_number, raw_line, s))
+                        .collect::<PuzzleResult<Vec<i64>>>()?;
+
+                    parse_state = State::TableName;
+                },
+
+                State::TableName => {
+                    let name = line
+                        .strip_suffix(" map:")
+                        .ok_or(PuzzleError::parse("expected a map name ending in ` map:`"))
+                        .at_line(line_number, raw_line)?;
+
+                    let (source, destination) = name
+                        .trim()
+                        .split_once("-to-")
+                        .ok_or(PuzzleError::parse("expected a map name like `source-to-destination map:`"))
+                        .at_token(line_number, raw_line, name)?;
+
+                    current_table = Some((line_number, raw_line, source, destination));
+                    parse_state = State::TableRow;
+                },
+
+                State::TableRow => {
+                    let row_data: Vec<i64> = line
+                        .split_ascii_whitespace()
+                        .map(|s| s.parse::<i64>()
+                            .context("mapping values must be integers")
+                            .at_token(line_number, raw_line, s))
+                        .collect::<PuzzleResult<Vec<i64>>>()?;
+
+                    if row_data.len() != 3 {
+                        return Err(PuzzleError::parse(format!("expected 3 mapping values, found {}", row_data.len()))
+                            .with_location(Location::token(line_number, raw_line, line)));
+                    }
+                    current_mapping.insert(row_data[1]..(row_data[1] + row_data[2]), row_data[0] - row_data[1]);
+                }
+            }
+        }
+
+        Ok((seeds, almanac))
+    }
+
+    fn part1((seeds, almanac): &Self::Input) -> PuzzleResult<Self::Part1> {
+        lowest_location(almanac, seed_numbers(seeds))
+    }
+
+    fn part2((seeds, almanac): &Self::Input) -> PuzzleResult<Self::Part2> {
+        lowest_location(almanac, seed_ranges(seeds)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn test_seeds_for_locations() {
+        let (seeds, almanac) = Day5::parse(SAMPLE).expect("Should parse");
+
+        // seeds 13 and 14 end up at locations 35 and 43, while 55 and 79 go
+        // to 86 and 82
+        let found = seeds_for_locations(&almanac, &seed_numbers(&seeds), 0..50).expect("Should trace back");
+        assert_eq!(found, IntervalSet::from(13..15));
+
+        // the lowest location from the ranges, 46, comes from seed 82 only
+        let ranges = seed_ranges(&seeds).expect("Should read ranges");
+        let found = seeds_for_locations(&almanac, &ranges, 0..47).expect("Should trace back");
+        assert_eq!(found, IntervalSet::from(82..83));
+
+        // every location some seed in the ranges reaches traces back to it
+        let found = seeds_for_locations(&almanac, &ranges, 0..i64::MAX).expect("Should trace back");
+        assert_eq!(found, ranges);
+    }
+
+    #[test]
+    fn test_parse_locations() {
+        assert_eq!(parse_locations("0..50").expect("Should parse"), 0..50);
+        assert!(parse_locations("50..0").is_err());
+        assert!(parse_locations("0-50").is_err());
+        assert!(parse_locations("a..b").is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use std::ops::Range;
+use aoc_utils::interval::{IntervalMap,IntervalSet};
+
+/// A function on numbers that moves each of a set of ranges by a fixed offset,
+/// leaving every other number where it is
+#[derive(Clone,Debug,Default,Eq,PartialEq)]
+pub struct Mapping {
+    /// Source ranges and how far they move. Ranges that don't move aren't
+    /// stored.
+    offsets: IntervalMap<i64,i64>
+}
+
+impl Mapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves every number in `source` by `offset`, replacing anything already
+    /// set for them
+    pub fn insert(&mut self, source: Range<i64>, offset: i64) {
+        if offset == 0 {
+            self.offsets.remove(source);
+        }
+        else {
+            self.offsets.insert(source, offset);
+        }
+    }
+
+    /// The source ranges that move and how far they move
+    pub fn iter(&self) -> impl Iterator<Item = (&Range<i64>, &i64)> {
+        self.offsets.iter()
+    }
+
+    pub fn apply(&self, value: i64) -> i64 {
+        value + self.offsets.get(&value).copied().unwrap_or_default()
+    }
+
+    /// Where every number in `range` ends up
+    pub fn apply_range(&self, range: Range<i64>) -> IntervalSet<i64> {
+        let mut destinations = self.offsets.gaps(range.clone());
+
+        for (source, offset) in self.offsets.overlapping(range) {
+            destinations.insert(source.start + offset..source.end + offset);
+        }
+
+        destinations
+    }
+
+    /// Where every number in `values` ends up
+    pub fn apply_set(&self, values: &IntervalSet<i64>) -> IntervalSet<i64> {
+        values
+            .iter()
+            .fold(IntervalSet::new(), |destinations, range| destinations.union(&self.apply_range(range.clone())))
+    }
+
+    /// Every number that ends up in `values`. Unlike [`Mapping::inverse`] this
+    /// works for any mapping, as it can give more than one source for a number.
+    pub fn preimage(&self, values: &IntervalSet<i64>) -> IntervalSet<i64> {
+        // numbers that don't move are their own source
+        let mut sources = values.difference(&self.offsets.domain());
+
+        for (source, offset) in self.offsets.iter() {
+            let destination = IntervalSet::from(source.start + offset..source.end + offset);
+            for range in &destination.intersection(values) {
+                sources.insert(range.start - offset..range.end - offset);
+            }
+        }
+
+        sources
+    }
+
+    /// The mapping that undoes this one, or `None` if some numbers end up in
+    /// the same place
+    pub fn inverse(&self) -> Option<Mapping> {
+        let sources = self.offsets.domain();
+        let destinations: IntervalSet<i64> = self.offsets
+            .iter()
+            .map(|(source, offset)| source.start + offset..source.end + offset)
+            .collect();
+
+        // the numbers that move must fill exactly the space they left, or
+        // they'd land on each other or on numbers that stay put
+        if destinations != sources || destinations.total_length() != sources.total_length() {
+            return None;
+        }
+
+        Some(self.offsets
+            .iter()
+            .map(|(source, offset)| (source.start + offset..source.end + offset, -offset))
+            .collect())
+    }
+
+    /// A single mapping that does this mapping followed by `next`
+    pub fn then(&self, next: &Mapping) -> Mapping {
+        let mut composed = Mapping::new();
+
+        // numbers this mapping moves go wherever `next` takes their destination
+        for (source, offset) in self.offsets.iter() {
+            let destination = source.start + offset..source.end + offset;
+
+            for (piece, next_offset) in next.offsets.overlapping(destination.clone()) {
+                composed.insert(piece.start - offset..piece.end - offset, offset + next_offset);
+            }
+
+            for gap in &next.offsets.gaps(destination) {
+                composed.insert(gap.start - offset..gap.end - offset, *offset);
+            }
+        }
+
+        // numbers this mapping leaves alone only go where `next` takes them
+        let moved = self.offsets.domain();
+        for (source, next_offset) in next.offsets.iter() {
+            for piece in &IntervalSet::from(source.clone()).difference(&moved) {
+                composed.insert(piece.clone(), *next_offset);
+            }
+        }
+
+        composed
+    }
+}
+
+impl FromIterator<(Range<i64>, i64)> for Mapping {
+    fn from_iter<I: IntoIterator<Item = (Range<i64>, i64)>>(iter: I) -> Self {
+        let mut mapping = Mapping::new();
+        for (source, offset) in iter {
+            mapping.insert(source, offset);
+        }
+        mapping
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_straddling_a_mapping() {
+        // 10..20 moves up by 100, and the query sticks out both ends of it
+        let mapping: Mapping = [(10..20, 100)].into_iter().collect();
+        let expected: IntervalSet<i64> = [5..10, 20..25, 110..120].into_iter().collect();
+        assert_eq!(mapping.apply_range(5..25), expected);
+    }
+
+    #[test]
+    fn test_then_matches_applying_in_turn() {
+        let first: Mapping = [(0..10, 5), (20..30, -15)].into_iter().collect();
+        let second: Mapping = [(3..12, 40), (25..28, -25)].into_iter().collect();
+        let composed = first.then(&second);
+
+        for value in -5..40 {
+            assert_eq!(composed.apply(value), second.apply(first.apply(value)), "mapping {}", value);
+        }
+    }
+
+    #[test]
+    fn test_preimage() {
+        // 0..10 lands on top of 10..20, which doesn't move, so 10..20 comes
+        // from both
+        let squash: Mapping = [(0..10, 10)].into_iter().collect();
+        let expected: IntervalSet<i64> = [2..5, 12..15].into_iter().collect();
+        assert_eq!(squash.preimage(&IntervalSet::from(12..15)), expected);
+        assert!(squash.preimage(&IntervalSet::from(0..10)).is_empty());
+    }
+
+    #[test]
+    fn test_inverse() {
+        // swaps 0..10 and 10..20
+        let swap: Mapping = [(0..10, 10), (10..20, -10)].into_iter().collect();
+        let inverse = swap.inverse().expect("Should be invertible");
+        for value in -5..25 {
+            assert_eq!(inverse.apply(swap.apply(value)), value);
+        }
+
+        // 0..10 lands on top of 10..20, which doesn't move
+        let squash: Mapping = [(0..10, 10)].into_iter().collect();
+        assert_eq!(squash.inverse(), None);
+    }
+}
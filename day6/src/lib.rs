@@ -0,0 +1,138 @@
+use aoc_utils::prelude::*;
+
+/// The fewest milliseconds the button can be held for to beat `distance` in a
+/// race lasting `time`, or `None` if the record can't be beaten. The longest
+/// is the same distance from the end, as the race is symmetric.
+pub fn shortest_winning_hold(time: u128, distance: u128) -> PuzzleResult<Option<u128>> {
+    let too_long = || PuzzleError::new(PuzzleErrorKind::InputError, format!("race of {} ms is too long to solve", time));
+    let beats = |hold: u128| hold * (time - hold) > distance;
+
+    // holding for h goes h * (time - h), which beats the distance between the
+    // roots of h^2 - time * h + distance
+    let squared = time.checked_mul(time).ok_or_else(too_long)?;
+    let Some(discriminant) = squared.checked_sub(distance.checked_mul(4).ok_or_else(too_long)?) else {
+        return Ok(None);
+    };
+
+    // the integer square root can put this one either side of the real root,
+    // so step to the first hold that wins
+    let mut hold = (time - discriminant.isqrt()) / 2;
+    while hold > 0 && beats(hold - 1) {
+        hold -= 1;
+    }
+    while hold <= time / 2 && !beats(hold) {
+        hold += 1;
+    }
+
+    Ok((hold <= time / 2).then_some(hold))
+}
+
+/// How many different hold times beat `distance` in a race lasting `time`
+pub fn race_ways_to_win(time: u128, distance: u128) -> PuzzleResult<u128> {
+    Ok(shortest_winning_hold(time, distance)?
+        .map(|hold| time - 2 * hold + 1)
+        .unwrap_or_default())
+}
+
+fn ways_to_win(time_line: &str, distance_line: &str) -> PuzzleResult<u128> {
+    let times: Vec<u128> = time_line
+        .strip_prefix("Time:")
+        .ok_or(PuzzleError::parse("expected a `Time:` line"))?
+        .split_ascii_whitespace()
+        .map(|s| s.parse::<u128>())
+        .collect::<Result<Vec<u128>,_>>()?;
+
+    let distances: Vec<u128> = distance_line
+        .strip_prefix("Distance:")
+        .ok_or(PuzzleError::parse("expected a `Distance:` line"))?
+        .split_ascii_whitespace()
+        .map(|s| s.parse::<u128>())
+        .collect::<Result<Vec<u128>,_>>()?;
+
+    if times.len() != distances.len() {
+        return Err(PuzzleError::parse("should be the same number of times and distances"));
+    }
+
+    times
+        .iter()
+        .zip(distances.iter())
+        .try_fold(1u128, |product, (t, d)| product
+            .checked_mul(race_ways_to_win(*t, *d)?)
+            .ok_or(PuzzleError::new(PuzzleErrorKind::InputError, "too many ways to win")))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+
+    type Input = (String, String);
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        let mut lines = input.lines();
+
+        let time_line = lines.next().ok_or(PuzzleErrorKind::ParseError)?;
+        let distance_line = lines.next().ok_or(PuzzleErrorKind::ParseError)?;
+
+        Ok((time_line.to_owned(), distance_line.to_owned()))
+    }
+
+    fn part1((time_line, distance_line): &Self::Input) -> PuzzleResult<Self::Part1> {
+        ways_to_win(time_line, distance_line)
+    }
+
+    fn part2((time_line, distance_line): &Self::Input) -> PuzzleResult<Self::Part2> {
+        // the kerning is bad, so the spaces between numbers don't count
+        let time_line: String = time_line.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        let distance_line: String = distance_line.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+
+        ways_to_win(&time_line, &distance_line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn brute_force(time: u128, distance: u128) -> u128 {
+        (0..=time).filter(|hold| hold * (time - hold) > distance).count() as u128
+    }
+
+    #[test]
+    fn test_exact_tie() {
+        // holding for 5 goes exactly 25, which isn't enough
+        assert_eq!(race_ways_to_win(10, 25).expect("Should solve"), 0);
+        assert_eq!(race_ways_to_win(10, 24).expect("Should solve"), 1);
+        assert_eq!(race_ways_to_win(30, 200).expect("Should solve"), 9);
+    }
+
+    #[test]
+    fn test_too_long() {
+        assert!(race_ways_to_win(u128::MAX, 1).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_brute_force(time in 0u128..300, distance in 0u128..25_000) {
+            prop_assert_eq!(race_ways_to_win(time, distance).expect("Should solve"), brute_force(time, distance));
+        }
+
+        #[test]
+        fn prop_large_races_are_on_the_boundary(time in 0u128..1 << 63, fraction in 0.0..0.25f64) {
+            // records anywhere up to the best possible distance
+            let distance = ((time * time) as f64 * fraction) as u128;
+            let hold = shortest_winning_hold(time, distance).expect("Should solve");
+
+            match hold {
+                Some(hold) => {
+                    prop_assert!(hold * (time - hold) > distance);
+                    prop_assert!(hold == 0 || (hold - 1) * (time - hold + 1) <= distance);
+                },
+                None => prop_assert!((time / 2) * (time - time / 2) <= distance)
+            }
+        }
+    }
+}
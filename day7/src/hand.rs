@@ -1,98 +1,86 @@
 use std::fmt::{self,Debug,Display};
-use aoc_utils::prelude::*;
 
 use crate::card::Card;
-#[derive(PartialOrd,Ord,Eq,PartialEq)]
+
+/// A hand, which ranks first by its type's place on the ladder and then card
+/// by card
+#[derive(Clone,PartialOrd,Ord,Eq,PartialEq)]
 pub struct Hand {
-    pub hand_type: HandType,
-    pub cards: [Card; 5]
+    /// Where the hand's type is on its ruleset's ladder, weakest first
+    pub rank: usize,
+    pub cards: Vec<Card>
 }
 
-#[derive(PartialOrd,Ord,PartialEq,Eq,Debug)]
-pub enum HandType {
-    HighCard,
-    OnePair,
-    TwoPair,
-    ThreeOfAKind,
-    FullHouse,
-    FourOfAKind,
-    FiveOfAKind,
+/// A rung of the hand type ladder, made by any hand whose cards group into
+/// `shape`
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct HandType {
+    pub name: String,
+    /// How many cards share each label, largest first
+    pub shape: Vec<usize>
 }
 
-impl Hand {
-    fn determine_hand_type(cards: &[Card; 5]) -> HandType {
-        let table: [u8; 15] = cards
-            .iter()
-            .fold([0u8; 15], |mut acc, c| { acc[c.0 as usize] += 1; acc });
+impl HandType {
+    pub fn new(name: &str, shape: &[usize]) -> Self {
+        let mut shape = shape.to_vec();
+        shape.sort_by(|a, b| b.cmp(a));
 
-        // count the number of unique cards, except jokers (jokers are at idx 1)
-        let counts: Vec<&u8> = table[2..]
-           .iter()
-           .filter(|c| **c > 0)
-           .collect();
+        HandType { name: name.to_owned(), shape }
+    }
 
-        if table[1] == 0 {
-            // no jokers
-            match counts.len() {
-                1 => HandType::FiveOfAKind,
-                2 => if counts.iter().any(|c| **c == 4) {
-                        HandType::FourOfAKind
-                    }
-                    else {
-                        HandType::FullHouse
-                    },
-                3 => if counts.iter().any(|c| **c == 2) {
-                        HandType::TwoPair
-                    }
-                    else {
-                        HandType::ThreeOfAKind
-                    },
-                4 => HandType::OnePair,
-                5 => HandType::HighCard,
-                _ => panic!("Impossibru")
-            }
-        }
-        else {
-            // yes jokers
-            match counts.len() {
-                0 => HandType::FiveOfAKind, // all jokers
-                1 => HandType::FiveOfAKind,
-                2 => if counts.iter().all(|c| **c == 2) {
-                        HandType::FullHouse
-                    }
-                    else {
-                        HandType::FourOfAKind
-                    }
-                3 => HandType::ThreeOfAKind,
-                4 => HandType::OnePair,
-                _ => panic!("Impossibru")
-            }
-        }
+    /// The usual hand types for five cards, from high card up to five of a
+    /// kind
+    pub fn standard_ladder() -> Vec<HandType> {
+        vec![
+            HandType::new("high card", &[1, 1, 1, 1, 1]),
+            HandType::new("one pair", &[2, 1, 1, 1]),
+            HandType::new("two pair", &[2, 2, 1]),
+            HandType::new("three of a kind", &[3, 1, 1]),
+            HandType::new("full house", &[3, 2]),
+            HandType::new("four of a kind", &[4, 1]),
+            HandType::new("five of a kind", &[5])
+        ]
     }
 
-    pub fn from_str(s: &str) -> Result<Self,PuzzleError> {
-        if s.len() != 5 {
-            return Err(PuzzleErrorKind::ParseError.into());
-        }
+    /// A hand type for every way of grouping `hand_size` cards, ranked the
+    /// same way as the standard ladder: by the largest group, then the next
+    /// largest and so on. Each is named after its shape, e.g. `3-2-1`.
+    pub fn ladder(hand_size: usize) -> Vec<HandType> {
+        let mut shapes = Vec::new();
+        partitions(hand_size, hand_size, &mut Vec::new(), &mut shapes);
+        shapes.sort();
 
-        let cards: Vec<Card> = s
-            .chars()
-            .map(|c| Card::from_char(c))
-            .collect::<Result<Vec<_>,_>>()?;
+        shapes
+            .into_iter()
+            .map(|shape| {
+                let name = shape.iter().map(usize::to_string).collect::<Vec<_>>().join("-");
+                HandType { name, shape }
+            })
+            .collect()
+    }
+}
 
-        let cards: [Card; 5] = cards
-            .try_into()
-            .map_err(|_| PuzzleError::from(PuzzleErrorKind::ParseError))?;
+/// Every way of writing `remaining` as a sum of parts no bigger than
+/// `largest`, largest part first
+pub(crate) fn partitions(remaining: usize, largest: usize, current: &mut Vec<usize>, all: &mut Vec<Vec<usize>>) {
+    if remaining == 0 {
+        all.push(current.clone());
+        return;
+    }
 
-        let hand_type = Self::determine_hand_type(&cards);
-        
-        Ok(Hand { cards, hand_type })
+    for part in (1..=largest.min(remaining)).rev() {
+        current.push(part);
+        partitions(remaining - part, part, current, all);
+        current.pop();
     }
 }
 
 impl Display for Hand {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}{}{}{}", self.cards[0], self.cards[1], self.cards[2], self.cards[3], self.cards[4])
+        for card in &self.cards {
+            write!(f, "{}", card)?;
+        }
+        Ok(())
     }
 }
 
@@ -101,3 +89,21 @@ impl Debug for Hand {
         write!(f, "{}", self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_ladder() {
+        let shapes: Vec<Vec<usize>> = HandType::ladder(5).into_iter().map(|t| t.shape).collect();
+        let standard: Vec<Vec<usize>> = HandType::standard_ladder().into_iter().map(|t| t.shape).collect();
+        assert_eq!(shapes, standard);
+
+        // 11 ways of grouping six cards
+        let six = HandType::ladder(6);
+        assert_eq!(six.len(), 11);
+        assert_eq!(six[0].name, "1-1-1-1-1-1");
+        assert_eq!(six[10].name, "6");
+    }
+}
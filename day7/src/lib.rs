@@ -0,0 +1,104 @@
+use aoc_utils::prelude::*;
+
+pub mod card;
+pub mod hand;
+pub mod ruleset;
+
+use hand::{Hand,HandType};
+use ruleset::Ruleset;
+
+/// Each hand's bid multiplied by its rank among all the hands under `ruleset`,
+/// added up
+pub fn total_winnings(lines: &[String], ruleset: &Ruleset) -> PuzzleResult<u64> {
+    let mut hands_and_bids: Vec<(Hand,u64)> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let (hand, bid) = line
+            .split_once(' ')
+            .ok_or(PuzzleError::parse("expected `HAND BID`"))
+            .at_line(index + 1, line)?;
+        let hand = ruleset.hand(hand).at_token(index + 1, line, hand)?;
+        let bid = bid
+            .trim()
+            .parse::<u64>()
+            .context("bid must be a whole number")
+            .at_token(index + 1, line, bid)?;
+
+        hands_and_bids.push((hand, bid));
+    }
+
+    hands_and_bids.sort();
+
+    Ok(hands_and_bids
+        .iter()
+        .enumerate()
+        .map(|(idx, (_hand, bid))| (idx + 1) as u64 * bid)
+        .sum())
+}
+
+/// The total winnings of the same hands under each of `rulesets`, to compare
+/// them side by side
+pub fn compare_rulesets<'a>(lines: &[String], rulesets: &'a [Ruleset]) -> PuzzleResult<Vec<(&'a str, u64)>> {
+    rulesets
+        .iter()
+        .map(|ruleset| Ok((ruleset.name(), total_winnings(lines, ruleset)?)))
+        .collect()
+}
+
+/// The rulesets `--compare` scores the hands under: both parts' rules, and
+/// jacks that are wild but keep their place in the order
+pub fn comparison_rulesets() -> Vec<Ruleset> {
+    vec![
+        Ruleset::standard(),
+        Ruleset::jokers(),
+        Ruleset::new("jacks wild", "23456789TJQKA", "J", HandType::standard_ladder())
+            .expect("the jacks wild ruleset is valid")
+    ]
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+
+    /// `--compare` lists the total winnings under each of
+    /// [`comparison_rulesets`] instead of solving the puzzle
+    const OPTIONS: &'static [PuzzleOption] = &[PuzzleOption::Flag("compare")];
+
+    type Input = Vec<String>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        Ok(input
+            .lines()
+            .map(str::to_owned)
+            .collect())
+    }
+
+    fn part1(lines: &Self::Input) -> PuzzleResult<Self::Part1> {
+        total_winnings(lines, &Ruleset::standard())
+    }
+
+    fn part2(lines: &Self::Input) -> PuzzleResult<Self::Part2> {
+        total_winnings(lines, &Ruleset::jokers())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_rulesets() {
+        let lines: Vec<String> = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483"
+            .lines()
+            .map(str::to_owned)
+            .collect();
+        let rulesets = comparison_rulesets();
+
+        let totals = compare_rulesets(&lines, &rulesets).expect("Should score");
+        assert_eq!(totals[0], ("standard", 6440));
+        assert_eq!(totals[1], ("jokers", 5905));
+        assert_eq!(totals[2].0, "jacks wild");
+    }
+}
@@ -1,40 +1,20 @@
-use std::io::BufRead;
+use std::io::Read;
 use aoc_utils::prelude::*;
-
-mod card;
-mod hand;
-
-use hand::Hand;
+use day7::{Day7,compare_rulesets,comparison_rulesets};
 
 fn main() -> PuzzleResult<()> {
-    let _timer = Timer::new();
-    let reader = get_puzzle_input()?;
-
-    let mut hands_and_bids: Vec<(Hand,u32)> = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-
-        let (hand, bid) = line.split_at(5);
-        let hand = Hand::from_str(hand)?;
-        let bid = u32::from_str_radix(&bid[1..], 10)?;
-
-        hands_and_bids.push((hand, bid));
+    let args = PuzzleArgs::from_env(Day7::OPTIONS)?;
+    if !args.flag("compare") {
+        return run_main::<Day7>(env!("CARGO_PKG_NAME"), PuzzlePart::from_feature(cfg!(feature = "part2")));
     }
 
-    hands_and_bids.sort();
+    let mut input = String::new();
+    args.input_source(env!("CARGO_PKG_NAME"))?.open()?.read_to_string(&mut input)?;
 
-    #[cfg(debug_assertions)]
-    for (h, _) in &hands_and_bids {
-        println!("{} ({:?})", h, h.hand_type);
+    let rulesets = comparison_rulesets();
+    for (name, total) in compare_rulesets(&Day7::parse(&input)?, &rulesets)? {
+        println!("{}: {}", name, total);
     }
 
-    let total_winnings: u32 = hands_and_bids
-        .iter()
-        .enumerate()
-        .map(|(idx, (_hand, bid))| (idx + 1) as u32 * bid)
-        .sum();
-
-    println!("Total winnings: {}", total_winnings);
-
     Ok(())
 }
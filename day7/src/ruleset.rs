@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use aoc_utils::prelude::*;
+use crate::card::Card;
+use crate::hand::{Hand,HandType,partitions};
+
+/// How hands of Camel Cards are read and ranked: the order of the cards,
+/// which of them are wild and the ladder of hand types
+#[derive(Clone,Debug)]
+pub struct Ruleset {
+    name: String,
+    /// Card labels, weakest first
+    order: Vec<char>,
+    wild: Vec<char>,
+    /// Hand types, weakest first
+    ladder: Vec<HandType>,
+    hand_size: usize
+}
+
+impl Ruleset {
+    /// Fails unless every card label is different, the wild cards are all in
+    /// `order`, and the ladder has exactly one type for every way of grouping
+    /// the same number of cards
+    pub fn new(name: &str, order: &str, wild: &str, ladder: Vec<HandType>) -> PuzzleResult<Self> {
+        let invalid = |message: String| PuzzleError::new(PuzzleErrorKind::InvalidArgument, message);
+
+        let order: Vec<char> = order.chars().collect();
+        if order.is_empty() {
+            return Err(invalid(format!("ruleset `{}` has no cards", name)));
+        }
+        if let Some(label) = order.iter().enumerate().find_map(|(i, c)| order[..i].contains(c).then_some(c)) {
+            return Err(invalid(format!("card `{}` appears more than once in ruleset `{}`", label, name)));
+        }
+
+        let wild: Vec<char> = wild.chars().collect();
+        if let Some(label) = wild.iter().find(|c| !order.contains(c)) {
+            return Err(invalid(format!("wild card `{}` is not in ruleset `{}`", label, name)));
+        }
+
+        let hand_size = ladder
+            .first()
+            .map(|t| t.shape.iter().sum())
+            .ok_or_else(|| invalid(format!("ruleset `{}` has no hand types", name)))?;
+
+        let mut shapes = Vec::new();
+        partitions(hand_size, hand_size, &mut Vec::new(), &mut shapes);
+        for shape in &shapes {
+            match ladder.iter().filter(|t| t.shape == *shape).count() {
+                0 => return Err(invalid(format!("ruleset `{}` has no hand type for {:?}", name, shape))),
+                1 => {},
+                _ => return Err(invalid(format!("ruleset `{}` has more than one hand type for {:?}", name, shape)))
+            }
+        }
+        if ladder.len() != shapes.len() {
+            return Err(invalid(format!("ruleset `{}` mixes hand types of different sizes", name)));
+        }
+
+        Ok(Ruleset { name: name.to_owned(), order, wild, ladder, hand_size })
+    }
+
+    /// Part 1's rules, where `J` is a jack
+    pub fn standard() -> Self {
+        Self::new("standard", "23456789TJQKA", "", HandType::standard_ladder())
+            .expect("the standard ruleset is valid")
+    }
+
+    /// Part 2's rules, where `J` is a joker: wild, but the weakest card
+    pub fn jokers() -> Self {
+        Self::new("jokers", "J23456789TQKA", "J", HandType::standard_ladder())
+            .expect("the jokers ruleset is valid")
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    pub fn hand_type(&self, hand: &Hand) -> &HandType {
+        &self.ladder[hand.rank]
+    }
+
+    pub fn is_wild(&self, card: Card) -> bool {
+        self.wild.contains(&card.label)
+    }
+
+    pub fn card(&self, label: char) -> PuzzleResult<Card> {
+        self.order
+            .iter()
+            .position(|c| *c == label)
+            .map(|strength| Card { strength, label })
+            .ok_or_else(|| PuzzleError::parse(format!("`{}` is not a card in ruleset `{}`", label, self.name)))
+    }
+
+    /// Where the type made by `cards` is on the ladder. Wild cards become
+    /// whichever cards make the best type, which is found by trying every way
+    /// of adding them to the groups of other cards or starting new groups, so
+    /// works for any ladder.
+    fn rank(&self, cards: &[Card]) -> usize {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        let mut wilds = 0;
+        for card in cards {
+            if self.is_wild(*card) {
+                wilds += 1;
+            }
+            else {
+                *counts.entry(card.label).or_default() += 1;
+            }
+        }
+
+        // a wild can become any card, including one that doesn't otherwise
+        // appear in the hand
+        let spare_labels = self.order.len() - counts.len();
+        let mut shape: Vec<usize> = counts.into_values().collect();
+        self.best_rank(&mut shape, wilds, spare_labels)
+    }
+
+    /// The best rank reachable by placing `wilds` more cards into `shape`
+    fn best_rank(&self, shape: &mut Vec<usize>, wilds: usize, spare_labels: usize) -> usize {
+        if wilds == 0 {
+            let mut sorted = shape.clone();
+            sorted.sort_by(|a, b| b.cmp(a));
+
+            return self.ladder
+                .iter()
+                .position(|t| t.shape == sorted)
+                .expect("the ladder has a type for every shape");
+        }
+
+        let mut best = None;
+        for group in 0..shape.len() {
+            // groups of the same size lead to the same shapes
+            if shape[..group].contains(&shape[group]) {
+                continue;
+            }
+
+            shape[group] += 1;
+            best = best.max(Some(self.best_rank(shape, wilds - 1, spare_labels)));
+            shape[group] -= 1;
+        }
+
+        if spare_labels > 0 {
+            shape.push(1);
+            best = best.max(Some(self.best_rank(shape, wilds - 1, spare_labels - 1)));
+            shape.pop();
+        }
+
+        best.expect("every card is in the order, so a wild can always be placed")
+    }
+
+    /// Reads a hand such as `KTJJT`
+    pub fn hand(&self, s: &str) -> PuzzleResult<Hand> {
+        let cards = s
+            .chars()
+            .map(|c| self.card(c))
+            .collect::<PuzzleResult<Vec<_>>>()?;
+
+        if cards.len() != self.hand_size {
+            return Err(PuzzleError::parse(format!("hands in ruleset `{}` have {} cards, `{}` has {}", self.name, self.hand_size, s, cards.len())));
+        }
+
+        Ok(Hand { rank: self.rank(&cards), cards })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jokers() {
+        let standard = Ruleset::standard();
+        let jokers = Ruleset::jokers();
+
+        let hand = standard.hand("KTJJT").expect("Should parse");
+        assert_eq!(standard.hand_type(&hand).name, "two pair");
+        let hand = jokers.hand("KTJJT").expect("Should parse");
+        assert_eq!(jokers.hand_type(&hand).name, "four of a kind");
+
+        // jokers are the weakest card when breaking ties
+        assert!(jokers.hand("JKKK2").expect("Should parse") < jokers.hand("QQQQ2").expect("Should parse"));
+        assert_eq!(jokers.hand_type(&jokers.hand("JJJJJ").expect("Should parse")).name, "five of a kind");
+    }
+
+    #[test]
+    fn test_variants() {
+        let wilds = Ruleset::new("wild twos and threes", "23456789TJQKA", "23", HandType::standard_ladder()).expect("Should build");
+        assert_eq!(wilds.hand_type(&wilds.hand("23AK4").expect("Should parse")).name, "three of a kind");
+
+        let six = Ruleset::new("six cards", "23456789TJQKA", "", HandType::ladder(6)).expect("Should build");
+        assert_eq!(six.hand_type(&six.hand("AAKKK2").expect("Should parse")).name, "3-2-1");
+        assert!(six.hand("AAKKK").is_err());
+    }
+
+    /// Every multiset of `size` labels from `labels`
+    fn multisets(labels: &[char], size: usize) -> Vec<String> {
+        if size == 0 {
+            return vec![String::new()];
+        }
+
+        (0..labels.len())
+            .flat_map(|i| multisets(&labels[i..], size - 1)
+                .into_iter()
+                .map(move |rest| format!("{}{}", labels[i], rest)))
+            .collect()
+    }
+
+    /// The best rank of `hand` found by trying every card for each wild
+    fn brute_force_rank(ruleset: &Ruleset, hand: &str) -> usize {
+        let natural = Ruleset::new("natural", &ruleset.order.iter().collect::<String>(), "", ruleset.ladder.clone())
+            .expect("Should build");
+        let fixed: String = hand.chars().filter(|c| !ruleset.wild.contains(c)).collect();
+
+        multisets(&ruleset.order, hand.len() - fixed.len())
+            .into_iter()
+            .map(|wilds| natural.hand(&format!("{}{}", fixed, wilds)).expect("Should parse").rank)
+            .max()
+            .expect("there is always at least one substitution")
+    }
+
+    #[test]
+    fn test_every_hand_matches_brute_force() {
+        let mut reversed = HandType::standard_ladder();
+        reversed.reverse();
+
+        let rulesets = [
+            Ruleset::jokers(),
+            Ruleset::new("reversed", "J23456789TQKA", "J", reversed).expect("Should build"),
+            Ruleset::new("two wilds", "J23456789TQKA", "J2", HandType::standard_ladder()).expect("Should build")
+        ];
+
+        for ruleset in &rulesets {
+            for hand in multisets(&ruleset.order, 5) {
+                let rank = ruleset.hand(&hand).expect("Should parse").rank;
+                assert_eq!(rank, brute_force_rank(ruleset, &hand), "{} under {}", hand, ruleset.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_wilds_on_a_custom_ladder() {
+        // two pair beats a full house, so the joker should start a new group
+        // rather than join a pair
+        let mut ladder = HandType::standard_ladder();
+        ladder.swap(2, 4);
+        let ruleset = Ruleset::new("two pair first", "J23456789TQKA", "J", ladder).expect("Should build");
+
+        let hand = ruleset.hand("AAKKJ").expect("Should parse");
+        assert_eq!(ruleset.hand_type(&hand).name, "two pair");
+        assert_eq!(ruleset.hand_type(&hand).shape, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn test_invalid_rulesets() {
+        let ladder = HandType::standard_ladder;
+        assert!(Ruleset::new("empty", "", "", ladder()).is_err());
+        assert!(Ruleset::new("repeated", "2342", "", ladder()).is_err());
+        assert!(Ruleset::new("missing wild", "234", "J", ladder()).is_err());
+        assert!(Ruleset::new("short ladder", "234", "", ladder()[1..].to_vec()).is_err());
+
+        let mut mixed = ladder();
+        mixed.push(HandType::new("six of a kind", &[6]));
+        assert!(Ruleset::new("mixed", "234", "", mixed).is_err());
+    }
+}
@@ -0,0 +1,473 @@
+use std::collections::HashMap;
+use std::fmt;
+use aoc_utils::cycle::{find_cycle,Cycle};
+use aoc_utils::math::{checked_crt,lcm,lcm_all};
+use aoc_utils::prelude::*;
+
+#[derive(Eq,PartialEq)]
+pub enum Instruction {
+    Left,
+    Right
+}
+
+#[derive(Eq,PartialEq)]
+pub struct Destination {
+    pub left: String,
+    pub right: String
+}
+
+impl TryFrom<char> for Instruction {
+    type Error = PuzzleError;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            _ => Err(PuzzleError::parse(format!("unknown instruction `{}`, expected `L` or `R`", value)))
+        }
+    }
+}
+
+fn parse_map_line(line_number: usize, line: &str) -> PuzzleResult<(String, Destination)> {
+    let (src, destinations) = line
+        .split_once('=')
+        .ok_or(PuzzleError::parse("expected `NODE = (LEFT, RIGHT)`"))
+        .at_line(line_number, line)?;
+
+    let destinations = destinations.trim();
+    let (left, right) = destinations
+        .strip_prefix('(')
+        .and_then(|d| d.strip_suffix(')'))
+        .and_then(|d| d.split_once(','))
+        .ok_or(PuzzleError::parse("expected destinations in the form `(LEFT, RIGHT)`"))
+        .at_token(line_number, line, destinations)?;
+
+    let node = |name: &str| {
+        let name = name.trim();
+        if name.is_empty() {
+            Err(PuzzleError::parse("node name is empty")).at_token(line_number, line, name)
+        }
+        else {
+            Ok(name.to_owned())
+        }
+    };
+
+    Ok((node(src)?, Destination { left: node(left)?, right: node(right)? }))
+}
+
+/// The node reached from `node` by following `instruction`
+fn step<'a>(map: &'a Network, node: &str, instruction: &Instruction) -> PuzzleResult<&'a str> {
+    let destination = map
+        .get(node)
+        .ok_or(PuzzleError::new(PuzzleErrorKind::InputError, format!("no node named `{}`", node)))?;
+
+    Ok(match instruction {
+        Instruction::Left => &destination.left,
+        Instruction::Right => &destination.right
+    })
+}
+
+/// How a ghost's walk is laid out: a tail of states it never returns to,
+/// then a loop it goes round forever
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct GhostCycle {
+    pub start: String,
+    /// Steps taken before the ghost enters its loop
+    pub tail: usize,
+    /// Steps round the loop
+    pub length: usize,
+    /// The steps in the tail at which the ghost is on a Z node
+    pub tail_arrivals: Vec<usize>,
+    /// How far round the loop the ghost is each time it is on a Z node
+    pub loop_arrivals: Vec<usize>
+}
+
+impl GhostCycle {
+    /// Follows a ghost from `start` until it is on the same node at the same
+    /// position in the instructions as before. As there are only so many of
+    /// those, every walk ends up in a loop.
+    pub fn analyse(start: &str, instructions: &[Instruction], map: &Network) -> Self {
+        let walk: Cycle<(&str, usize)> = find_cycle((start, 0), |(node, index)| {
+            // every destination was checked to exist when parsing
+            let destination = &map[*node];
+            let node = match instructions[*index] {
+                Instruction::Left => destination.left.as_str(),
+                Instruction::Right => destination.right.as_str()
+            };
+
+            (node, (index + 1) % instructions.len())
+        });
+
+        let arrivals = |states: &[(&str, usize)]| states
+            .iter()
+            .enumerate()
+            .filter(|(_, (node, _))| node.ends_with('Z'))
+            .map(|(steps, _)| steps)
+            .collect::<Vec<_>>();
+
+        let (tail, cycle) = walk.states().split_at(walk.start());
+        GhostCycle {
+            start: start.to_owned(),
+            tail: walk.start(),
+            length: walk.length(),
+            tail_arrivals: arrivals(tail),
+            loop_arrivals: arrivals(cycle)
+        }
+    }
+
+    /// Whether the ghost is on a Z node after `steps`
+    pub fn arrives_at(&self, steps: usize) -> bool {
+        if steps < self.tail {
+            self.tail_arrivals.contains(&steps)
+        }
+        else {
+            self.loop_arrivals.contains(&((steps - self.tail) % self.length))
+        }
+    }
+
+    /// The first step at which the ghost is on a Z node, if it ever is
+    pub fn first_arrival(&self) -> Option<usize> {
+        self.tail_arrivals
+            .first()
+            .copied()
+            .or(self.loop_arrivals.first().map(|offset| self.tail + offset))
+    }
+}
+
+/// The cycle analysis of every ghost, one starting on each node ending in A
+pub fn analyse_ghosts(instructions: &[Instruction], map: &Network) -> Vec<GhostCycle> {
+    let mut starts: Vec<&String> = map
+        .keys()
+        .filter(|k| k.ends_with('A'))
+        .collect();
+    starts.sort();
+
+    starts
+        .into_iter()
+        .map(|start| GhostCycle::analyse(start, instructions, map))
+        .collect()
+}
+
+/// How many ways of lining up the ghosts' arrivals to keep track of before
+/// checking steps one by one instead
+const CANDIDATE_LIMIT: usize = 10_000;
+
+fn too_big() -> PuzzleError {
+    PuzzleError::new(PuzzleErrorKind::InputError, "the ghosts' loops are too long to line up")
+}
+
+/// The first step at which every ghost is on a Z node at the same time, or
+/// `None` if they never are. Fails if the step is too big to work out.
+pub fn simultaneous_arrival(ghosts: &[GhostCycle]) -> PuzzleResult<Option<u64>> {
+    let Some(latest_tail) = ghosts.iter().map(|g| g.tail).max() else {
+        return Ok(None);
+    };
+
+    // before every ghost is in its loop, just check each step
+    if let Some(steps) = (0..latest_tail).find(|&steps| ghosts.iter().all(|g| g.arrives_at(steps))) {
+        return Ok(Some(steps as u64));
+    }
+
+    // after that, each ghost is on a Z node at fixed points round its loop.
+    // Line those up one ghost at a time: every solution so far has the same
+    // period, so solutions that meet again are the same solution.
+    let mut solutions = vec![(0i128, 1i128)];
+    for (index, ghost) in ghosts.iter().enumerate() {
+        let period = ghost.length as i128;
+        let mut merged = Vec::new();
+
+        for solution in &solutions {
+            for offset in &ghost.loop_arrivals {
+                let arrival = (ghost.tail + offset) as i128;
+                if let Some(solution) = checked_crt(&[*solution, (arrival, period)]).map_err(|_| too_big())? {
+                    merged.push(solution);
+                }
+            }
+        }
+
+        merged.sort();
+        merged.dedup();
+        if merged.is_empty() {
+            return Ok(None);
+        }
+        if merged.len() > CANDIDATE_LIMIT {
+            return search_steps(&solutions, &ghosts[index..], latest_tail);
+        }
+        solutions = merged;
+    }
+
+    // a solution may come round before the last ghost has reached its loop,
+    // in which case the next time round counts
+    let latest_tail = latest_tail as i128;
+    let steps = solutions
+        .into_iter()
+        .map(|(steps, period)| if steps < latest_tail {
+                ((latest_tail - steps - 1) / period + 1)
+                    .checked_mul(period)
+                    .and_then(|wait| wait.checked_add(steps))
+            }
+            else {
+                Some(steps)
+            })
+        .collect::<Option<Vec<i128>>>()
+        .ok_or_else(too_big)?
+        .into_iter()
+        .min()
+        .expect("there is at least one solution");
+
+    u64::try_from(steps).map(Some).map_err(|_| too_big())
+}
+
+/// Goes through the steps from `latest_tail` on that match one of
+/// `solutions`, which all share a period, until every ghost in `rest` is also
+/// on a Z node. Once all the loops have come round together the steps
+/// repeat, so there is no point looking any further.
+fn search_steps(solutions: &[(i128, i128)], rest: &[GhostCycle], latest_tail: usize) -> PuzzleResult<Option<u64>> {
+    let period = solutions[0].1;
+    let repeat = rest
+        .iter()
+        .try_fold(period, |repeat, ghost| lcm(repeat, ghost.length as i128));
+
+    let first = latest_tail as i128 / period * period;
+    let mut base = first;
+    while repeat.is_none_or(|repeat| base - first < repeat) {
+        for (offset, _) in solutions {
+            let steps = base + offset;
+            if steps < latest_tail as i128 {
+                continue;
+            }
+
+            let steps = usize::try_from(steps).map_err(|_| too_big())?;
+            if rest.iter().all(|g| g.arrives_at(steps)) {
+                return Ok(Some(steps as u64));
+            }
+        }
+
+        base = base.checked_add(period).ok_or_else(too_big)?;
+    }
+
+    Ok(None)
+}
+
+/// The answer found by assuming each ghost's first Z node comes round again
+/// every time it has taken that many steps, which is how the puzzle inputs
+/// happen to be made
+pub fn lcm_shortcut(ghosts: &[GhostCycle]) -> Option<u64> {
+    ghosts
+        .iter()
+        .map(|g| g.first_arrival().map(|steps| steps as u64))
+        .collect::<Option<Vec<u64>>>()
+        .and_then(lcm_all)
+}
+
+/// When the ghosts are first all on Z nodes, and what the lcm shortcut would
+/// have said instead
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub struct Arrival {
+    pub steps: u64,
+    /// See [`lcm_shortcut`]
+    pub shortcut: Option<u64>
+}
+
+impl Arrival {
+    pub fn shortcut_holds(&self) -> bool {
+        self.shortcut == Some(self.steps)
+    }
+}
+
+/// Shows the step, and what the shortcut gives if it's different
+impl fmt::Display for Arrival {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.steps)?;
+
+        match self.shortcut {
+            _ if self.shortcut_holds() => Ok(()),
+            Some(shortcut) => write!(f, " (the lcm shortcut gives {})", shortcut),
+            None => write!(f, " (the lcm shortcut overflows)")
+        }
+    }
+}
+
+pub type Network = HashMap<String,Destination>;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+
+    type Input = (Vec<Instruction>, Network);
+    type Part1 = u64;
+    type Part2 = Arrival;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        let mut lines = input.lines().enumerate();
+
+        let (_, first_line) = lines
+            .next()
+            .ok_or(PuzzleError::new(PuzzleErrorKind::MissingInput, "no instructions"))?;
+
+        let instructions = first_line
+            .char_indices()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(i, c)| Instruction::try_from(c)
+                .at(Location::span(1, first_line, i..i + c.len_utf8())))
+            .collect::<Result<Vec<Instruction>,PuzzleError>>()?;
+
+        if instructions.is_empty() {
+            return Err(PuzzleError::new(PuzzleErrorKind::MissingInput, "no instructions")).at_line(1, first_line);
+        }
+
+        let map: Network = lines
+            .filter(|(_, x)| !x.trim().is_empty())
+            .map(|(index, line)| parse_map_line(index + 1, line))
+            .collect::<Result<Network,_>>()?;
+
+        let missing = map
+            .values()
+            .flat_map(|d| [&d.left, &d.right])
+            .find(|node| !map.contains_key(*node));
+
+        if let Some(node) = missing {
+            return Err(PuzzleError::new(PuzzleErrorKind::InputError, format!("no node named `{}`", node)));
+        }
+
+        Ok((instructions, map))
+    }
+
+    fn part1((instructions, map): &Self::Input) -> PuzzleResult<Self::Part1> {
+        let mut steps = 0;
+        let mut current_key = "AAA";
+
+        while current_key != "ZZZ" {
+            current_key = step(map, current_key, &instructions[steps % instructions.len()])?;
+            steps += 1;
+        }
+
+        Ok(steps as u64)
+    }
+
+    fn part2((instructions, map): &Self::Input) -> PuzzleResult<Self::Part2> {
+        let ghosts = analyse_ghosts(instructions, map);
+        if ghosts.is_empty() {
+            return Err(PuzzleError::new(PuzzleErrorKind::InputError, "no nodes ending in A"));
+        }
+
+        let steps = simultaneous_arrival(&ghosts)?
+            .ok_or(PuzzleError::new(PuzzleErrorKind::InputError, "the ghosts are never all on Z nodes at once"))?;
+
+        Ok(Arrival { steps, shortcut: lcm_shortcut(&ghosts) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_cycles() {
+        let input = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)";
+        let (instructions, map) = Day8::parse(input).expect("Should parse");
+
+        let ghosts = analyse_ghosts(&instructions, &map);
+        assert_eq!(ghosts[1], GhostCycle {
+            start: "22A".to_owned(),
+            tail: 1,
+            length: 6,
+            tail_arrivals: vec![],
+            loop_arrivals: vec![2, 5]
+        });
+        assert_eq!(simultaneous_arrival(&ghosts).expect("Should fit"), Some(6));
+        assert_eq!(lcm_shortcut(&ghosts), Some(6));
+        assert_eq!(Day8::part2(&(instructions, map)).expect("Should solve").to_string(), "6");
+    }
+
+    #[test]
+    fn test_shortcut_differs() {
+        // 1A is on a Z node at odd steps, 2A at 2, 5, 8 and so on
+        let input = "L\n\n1A = (1Z, 1Z)\n1Z = (1B, 1B)\n1B = (1Z, 1Z)\n2A = (2B, 2B)\n2B = (2Z, 2Z)\n2Z = (2C, 2C)\n2C = (2D, 2D)\n2D = (2Z, 2Z)";
+        let (instructions, map) = Day8::parse(input).expect("Should parse");
+
+        let ghosts = analyse_ghosts(&instructions, &map);
+        assert_eq!(lcm_shortcut(&ghosts), Some(2));
+        assert_eq!(simultaneous_arrival(&ghosts).expect("Should fit"), Some(5));
+        assert!(ghosts.iter().all(|g| g.arrives_at(5)));
+
+        let arrival = Day8::part2(&(instructions, map)).expect("Should solve");
+        assert!(!arrival.shortcut_holds());
+        assert_eq!(arrival.to_string(), "5 (the lcm shortcut gives 2)");
+    }
+
+    #[test]
+    fn test_never_together() {
+        // 1A is on a Z node at even steps, 2A at odd ones
+        let input = "L\n\n1A = (1B, 1B)\n1B = (1Z, 1Z)\n1Z = (1B, 1B)\n2A = (2Z, 2Z)\n2Z = (2B, 2B)\n2B = (2Z, 2Z)";
+        let (instructions, map) = Day8::parse(input).expect("Should parse");
+
+        let ghosts = analyse_ghosts(&instructions, &map);
+        assert_eq!(lcm_shortcut(&ghosts), Some(2));
+        assert_eq!(simultaneous_arrival(&ghosts).expect("Should fit"), None);
+        assert!(Day8::part2(&(instructions, map)).is_err());
+    }
+
+    /// A ghost that is on a Z node at every step of a loop of `length`
+    fn everywhere(name: &str, length: usize) -> GhostCycle {
+        GhostCycle { start: name.to_owned(), tail: 0, length, tail_arrivals: vec![], loop_arrivals: (0..length).collect() }
+    }
+
+    #[test]
+    fn test_many_arrivals() {
+        // 30 ghosts with 6 arrivals each would be 6^30 combinations, but they
+        // all line up with each other
+        let mut ghosts = vec![everywhere("1A", 6); 30];
+        ghosts.push(GhostCycle { start: "2A".to_owned(), tail: 3, length: 4, tail_arrivals: vec![], loop_arrivals: vec![1] });
+
+        assert_eq!(simultaneous_arrival(&ghosts).expect("Should fit"), Some(4));
+    }
+
+    /// The first step at which every ghost is on a Z node, found by checking
+    /// every step up to `limit`
+    fn brute_force(ghosts: &[GhostCycle], limit: usize) -> Option<u64> {
+        (0..limit)
+            .find(|&steps| ghosts.iter().all(|g| g.arrives_at(steps)))
+            .map(|steps| steps as u64)
+    }
+
+    #[test]
+    fn test_coprime_loops_with_many_arrivals() {
+        // 5^6 ways of lining up the arrivals is more than CANDIDATE_LIMIT
+        let ghosts: Vec<GhostCycle> = [7, 11, 13, 17, 19, 23]
+            .into_iter()
+            .enumerate()
+            .map(|(index, length)| GhostCycle {
+                start: format!("{}A", index),
+                tail: index,
+                length,
+                tail_arrivals: vec![],
+                loop_arrivals: (0..5).map(|n| (n * 3 + index) % length).collect()
+            })
+            .collect();
+        assert!(ghosts.iter().map(|g| g.loop_arrivals.len()).product::<usize>() > CANDIDATE_LIMIT);
+
+        let expected = brute_force(&ghosts, 7 * 11 * 13 * 17 * 19 * 23 + 5);
+        assert!(expected.is_some());
+        assert_eq!(simultaneous_arrival(&ghosts).expect("Should fit"), expected);
+
+        // two more ghosts, one on a Z node at even steps and one at odd ones,
+        // can never line up, which is only found once the steps repeat
+        let mut never = ghosts.clone();
+        never.push(GhostCycle { start: "6A".to_owned(), tail: 0, length: 2, tail_arrivals: vec![], loop_arrivals: vec![0] });
+        never.push(GhostCycle { start: "7A".to_owned(), tail: 0, length: 2, tail_arrivals: vec![], loop_arrivals: vec![1] });
+        assert_eq!(simultaneous_arrival(&never).expect("Should fit"), None);
+    }
+
+    #[test]
+    fn test_loops_too_long() {
+        // the product of the first 30 primes is far too big for an i128
+        let primes = (2usize..).filter(|n| (2..*n).all(|d| n % d != 0)).take(30);
+        let ghosts: Vec<GhostCycle> = primes
+            .map(|p| GhostCycle { start: format!("{}A", p), tail: 0, length: p, tail_arrivals: vec![], loop_arrivals: vec![1] })
+            .collect();
+
+        let error = simultaneous_arrival(&ghosts).expect_err("Should overflow");
+        assert!(error.message().is_some_and(|m| m.contains("too long")), "{}", error);
+    }
+}
@@ -0,0 +1,70 @@
+use aoc_utils::prelude::*;
+
+/// Extrapolates the next value in the sequence, or the previous value if
+/// `backwards` is set
+fn extrapolate(b: &[i32], backwards: bool) -> i32 {
+    let mut diffs: Vec<Vec<i32>> = Vec::new();
+    let mut current = b.to_vec();
+
+    loop {
+        let x: Vec<i32> = current
+            .windows(2)
+            .map(|i| i[1] - i[0])
+            .collect();
+        diffs.insert(0, current);
+        current = x;
+
+        if current.iter().all(|x| *x == 0) {
+            break
+        }
+    }
+
+    if !backwards {
+        diffs
+            .iter()
+            .map(|v| v.last().unwrap())
+            .sum()
+    }
+    else {
+        diffs
+            .iter()
+            .fold(0, |acc,i| i.first().unwrap() - acc)
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+
+    type Input = Vec<Vec<i32>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> PuzzleResult<Self::Input> {
+        Ok(input
+            .lines()
+            .map(|x| x
+                .split_ascii_whitespace()
+                .map(|s| s.parse::<i32>())
+                .collect::<Result<Vec<i32>,_>>())
+            .collect::<Result<Vec<Vec<i32>>,_>>()?
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .collect())
+    }
+
+    fn part1(sequences: &Self::Input) -> PuzzleResult<Self::Part1> {
+        Ok(sequences
+            .iter()
+            .map(|x| extrapolate(x, false))
+            .sum())
+    }
+
+    fn part2(sequences: &Self::Input) -> PuzzleResult<Self::Part2> {
+        Ok(sequences
+            .iter()
+            .map(|x| extrapolate(x, true))
+            .sum())
+    }
+}